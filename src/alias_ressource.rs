@@ -0,0 +1,90 @@
+use crate::{
+    backend::Backend,
+    path::RessourcePath,
+    traits::{ReadableRessource, RessourceType, WritableRessource},
+};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AliasRessourceError {
+    #[error("AliasRessource: IO Error reading alias at {path}. Error: {error}")]
+    Reading {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+
+    #[error("AliasRessource: IO Error writing alias at {path}. Error: {error}")]
+    Writing {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+
+    #[error("AliasRessource: Malformed alias at {path}. Error: {error}")]
+    Format {
+        path: PathBuf,
+        error: serde_json::Error,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AliasRessource {
+    pub target: RessourcePath,
+}
+
+impl AliasRessource {
+    pub fn new(target: RessourcePath) -> Self {
+        AliasRessource { target }
+    }
+}
+
+impl RessourceType for AliasRessource {
+    fn id() -> &'static str {
+        "core/alias"
+    }
+
+    fn display_name() -> &'static str {
+        "Alias"
+    }
+}
+
+impl ReadableRessource for AliasRessource {
+    type Error = AliasRessourceError;
+    async fn read<B: Backend>(backend: &B, path: &Path) -> Result<Self, AliasRessourceError> {
+        let data = backend
+            .read(path)
+            .await
+            .map_err(|e| AliasRessourceError::Reading {
+                path: path.to_path_buf(),
+                error: e,
+            })?;
+
+        serde_json::from_slice(&data).map_err(|e| AliasRessourceError::Format {
+            path: path.to_path_buf(),
+            error: e,
+        })
+    }
+}
+
+impl WritableRessource for AliasRessource {
+    type Error = AliasRessourceError;
+    async fn write<B: Backend>(&self, backend: &B, path: &Path) -> Result<(), AliasRessourceError> {
+        let data = serde_json::to_vec(self).map_err(|e| AliasRessourceError::Format {
+            path: path.to_path_buf(),
+            error: e,
+        })?;
+
+        backend
+            .write(path, &data)
+            .await
+            .map_err(|e| AliasRessourceError::Writing {
+                path: path.to_path_buf(),
+                error: e,
+            })
+    }
+
+    fn data_extension() -> &'static str {
+        "json"
+    }
+}
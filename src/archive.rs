@@ -0,0 +1,536 @@
+use crate::{
+    backend::{Backend, RetryBackend},
+    error::{RessourceError, RessourceResult},
+    folder_ressource::FolderRessource,
+    journal::JournalOperation,
+    meta::{MetaRessource, RessourceMetadata},
+    path::{NamingScheme, RessourceId, RessourcePath},
+    ressources::Ressources,
+    traits::RessourceType,
+};
+use futures::StreamExt;
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Component, Path, PathBuf},
+};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite};
+use tokio_tar::{Archive, Builder, EntryType, Header};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    Skip,
+    Overwrite,
+    Error,
+}
+
+const SPOOL_CHUNK: usize = 64 * 1024;
+
+struct ArchiveFile {
+    is_dir: bool,
+    spooled: PathBuf,
+    size: u64,
+}
+
+struct Import {
+    ids: Vec<RessourceId>,
+    metadata: RessourceMetadata,
+    metadata_bytes: Vec<u8>,
+    data_entry: PathBuf,
+}
+
+fn is_valid_extension(extension: &str) -> bool {
+    !extension.contains('/')
+        && !extension.contains(std::path::MAIN_SEPARATOR)
+        && !extension.contains("..")
+}
+
+fn metadata_entry_ids(entry: &Path, naming: NamingScheme) -> Option<Vec<RessourceId>> {
+    let mut components = entry
+        .components()
+        .map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let id = naming.id_from_metadata_file_name(components.pop()?)?;
+    let mut ids = components
+        .into_iter()
+        .map(|folder| naming.id_from_folder_name(folder).map(str::to_string))
+        .collect::<Option<Vec<_>>>()?;
+    ids.push(id.to_string());
+
+    ids.iter()
+        .all(|id| RessourcePath::is_valid_id(id))
+        .then_some(ids)
+}
+
+fn archive_naming(naming: NamingScheme) -> NamingScheme {
+    match naming {
+        NamingScheme::Index => NamingScheme::Suffix,
+        naming => naming,
+    }
+}
+
+fn relative<'a>(base: &Path, path: &'a Path) -> &'a Path {
+    path.strip_prefix(base).unwrap_or(path)
+}
+
+async fn append<W: AsyncWrite + Unpin + Send>(
+    builder: &mut Builder<W>,
+    entry_type: EntryType,
+    path: &Path,
+    data: &[u8],
+    metadata: &RessourceMetadata,
+) -> std::io::Result<()> {
+    let mut header = Header::new_gnu();
+    header.set_entry_type(entry_type);
+    header.set_size(data.len() as u64);
+    header.set_mtime(metadata.time.timestamp().max(0) as u64);
+    header.set_mode(if entry_type.is_dir() { 0o755 } else { 0o644 });
+    builder.append_data(&mut header, path, data).await
+}
+
+impl<B: Backend> Ressources<B> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            err,
+            fields(path = %path.resolve().display())
+        )
+    )]
+    pub async fn export_tar<W: AsyncWrite + Unpin + Send>(
+        &self,
+        path: RessourcePath,
+        writer: W,
+    ) -> RessourceResult<()> {
+        let base = MetaRessource::<FolderRessource>::load(self, path.clone())
+            .await?
+            .data_path();
+        let mut builder = Builder::new_non_terminated(writer);
+
+        for (ressource_path, metadata) in self.walk(path.clone()).await? {
+            let metadata_path = archive_naming(self.naming).metadata_path(&ressource_path);
+            let metadata_bytes = self
+                .read_metadata_file(&ressource_path)
+                .await
+                .map_err(|e| RessourceError::metadata_io(e, &ressource_path))?;
+
+            append(
+                &mut builder,
+                EntryType::Regular,
+                relative(&base, &metadata_path),
+                &metadata_bytes,
+                &metadata,
+            )
+            .await
+            .map_err(|e| RessourceError::Archive {
+                error: e,
+                ressource_path: ressource_path.clone(),
+                path: metadata_path.clone(),
+            })?;
+
+            let data_path = metadata.data_path(&ressource_path);
+            let (entry_type, data) = if metadata.type_id == FolderRessource::id() {
+                (EntryType::Directory, Vec::new())
+            } else {
+                let data =
+                    self.backend.read(&data_path).await.map_err(|e| {
+                        RessourceError::read_data_io(e, &ressource_path, &data_path)
+                    })?;
+                (EntryType::Regular, data)
+            };
+
+            append(
+                &mut builder,
+                entry_type,
+                relative(&base, &data_path),
+                &data,
+                &metadata,
+            )
+            .await
+            .map_err(|e| RessourceError::Archive {
+                error: e,
+                ressource_path: ressource_path.clone(),
+                path: data_path.clone(),
+            })?;
+        }
+
+        builder.finish().await.map_err(|e| RessourceError::Archive {
+            error: e,
+            path: path.resolve(),
+            ressource_path: path,
+        })
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            err,
+            fields(dest = %dest.resolve().display())
+        )
+    )]
+    pub async fn import_tar<R: AsyncRead + Unpin>(
+        &self,
+        dest: RessourcePath,
+        reader: R,
+        policy: ConflictPolicy,
+    ) -> RessourceResult<()> {
+        self.check_writable(&dest)?;
+        let dest_dir = MetaRessource::<FolderRessource>::load(self, dest.clone())
+            .await?
+            .data_path();
+        let spool = dest_dir.join(format!(".import-{}", Uuid::new_v4()));
+        self.backend
+            .create_dir(&spool)
+            .await
+            .map_err(|e| RessourceError::Archive {
+                error: e,
+                ressource_path: dest.clone(),
+                path: spool.clone(),
+            })?;
+
+        let imported = self.import_spooled(&dest, &spool, reader, policy).await;
+        let _ = self.remove_spool(&spool).await;
+        imported
+    }
+
+    async fn remove_spool(&self, spool: &Path) -> std::io::Result<()> {
+        let mut names = self.backend.read_dir(spool).await?;
+        while let Some(name) = names.next().await {
+            self.backend.remove_file(&spool.join(name?)).await?;
+        }
+        self.backend.remove_dir(spool).await
+    }
+
+    async fn spool_entry<E: AsyncRead + Unpin>(
+        &self,
+        entry: &mut E,
+        spooled: &Path,
+    ) -> std::io::Result<u64> {
+        self.backend.write(spooled, &[]).await?;
+        let mut buffer = vec![0; SPOOL_CHUNK];
+        let mut size = 0;
+        loop {
+            let read = entry.read(&mut buffer).await?;
+            if read == 0 {
+                return Ok(size);
+            }
+            self.backend.append(spooled, &buffer[..read]).await?;
+            size += read as u64;
+        }
+    }
+
+    async fn import_spooled<R: AsyncRead + Unpin>(
+        &self,
+        dest: &RessourcePath,
+        spool: &Path,
+        reader: R,
+        policy: ConflictPolicy,
+    ) -> RessourceResult<()> {
+        let archive_error = |e, path: &Path| RessourceError::Archive {
+            error: e,
+            ressource_path: dest.clone(),
+            path: path.to_path_buf(),
+        };
+
+        let mut archive = Archive::new(reader);
+        let mut entries = archive
+            .entries()
+            .map_err(|e| archive_error(e, &dest.resolve()))?;
+        let mut files = HashMap::new();
+        let mut order = Vec::new();
+
+        while let Some(entry) = entries.next().await {
+            let mut entry = entry.map_err(|e| archive_error(e, &dest.resolve()))?;
+            let path = entry
+                .path()
+                .map_err(|e| archive_error(e, &dest.resolve()))?
+                .into_owned();
+            let is_dir = entry.header().entry_type().is_dir();
+            let spooled = spool.join(order.len().to_string());
+            let size = if is_dir {
+                0
+            } else {
+                self.spool_entry(&mut entry, &spooled)
+                    .await
+                    .map_err(|e| archive_error(e, &spooled))?
+            };
+            files.insert(
+                path.clone(),
+                ArchiveFile {
+                    is_dir,
+                    spooled,
+                    size,
+                },
+            );
+            order.push(path);
+        }
+
+        let naming = archive_naming(self.naming);
+        let mut imports = Vec::new();
+        let mut claimed = HashSet::new();
+        for entry in order.iter().filter(|entry| {
+            entry
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| naming.id_from_metadata_file_name(name).is_some())
+        }) {
+            let invalid_entry = || RessourceError::InvalidArchiveEntry {
+                ressource_path: dest.clone(),
+                entry: entry.clone(),
+            };
+            let ids = metadata_entry_ids(entry, naming).ok_or_else(invalid_entry)?;
+            self.check_id(&dest.with_children(&mut ids.clone()))?;
+            let relative_path = RessourcePath::from_vec(PathBuf::new(), ids.clone());
+            let spooled = &files[entry].spooled;
+            let metadata_bytes = self
+                .backend
+                .read(spooled)
+                .await
+                .map_err(|e| archive_error(e, spooled))?;
+            let metadata: RessourceMetadata =
+                serde_json::from_slice(&metadata_bytes).map_err(|e| {
+                    RessourceError::MetadataFormat {
+                        error: e,
+                        ressource_path: dest.with_children(&mut ids.clone()),
+                        path: entry.clone(),
+                    }
+                })?;
+
+            if ids.last() != Some(&metadata.id) || !is_valid_extension(&metadata.data_extension) {
+                return Err(invalid_entry());
+            }
+
+            let data_entry = metadata.data_path(&relative_path);
+            let is_folder = metadata.type_id == FolderRessource::id();
+            if files.get(&data_entry).map(|file| file.is_dir) != Some(is_folder) {
+                return Err(RessourceError::ArchiveDataMismatch {
+                    ressource_path: dest.with_children(&mut ids.clone()),
+                    entry: entry.clone(),
+                    expected: data_entry,
+                });
+            }
+
+            claimed.insert(entry.clone());
+            claimed.insert(data_entry.clone());
+            imports.push(Import {
+                ids,
+                metadata,
+                metadata_bytes,
+                data_entry,
+            });
+        }
+
+        if let Some(entry) = order.iter().find(|entry| !claimed.contains(*entry)) {
+            return Err(RessourceError::InvalidArchiveEntry {
+                ressource_path: dest.clone(),
+                entry: entry.clone(),
+            });
+        }
+
+        imports.sort_by_key(|import| import.ids.len());
+        for mut import in imports {
+            let target = dest.with_children(&mut import.ids);
+            self.check_type(&target, &import.metadata.type_id)?;
+            let is_folder = import.metadata.type_id == FolderRessource::id();
+            let data_path = import.metadata.data_path(&target);
+            let data = &files[&import.data_entry];
+
+            let exists = self.exists(&target).await?;
+            if exists {
+                match policy {
+                    ConflictPolicy::Skip => continue,
+                    ConflictPolicy::Error => {
+                        return Err(RessourceError::ressource_exists(&target));
+                    }
+                    ConflictPolicy::Overwrite => {
+                        let existing = RessourceMetadata::load(self, &target).await?;
+                        if (existing.type_id == FolderRessource::id()) != is_folder {
+                            return Err(RessourceError::ressource_exists(&target));
+                        }
+
+                        let existing_data_path = existing.data_path(&target);
+                        if !is_folder && existing_data_path != data_path {
+                            self.backend
+                                .remove_file(&existing_data_path)
+                                .await
+                                .map_err(|e| {
+                                    RessourceError::write_data_io(e, &target, &existing_data_path)
+                                })?;
+                        }
+                    }
+                }
+            }
+
+            if let Some(limit) = self.max_data_bytes
+                && data.size > limit
+            {
+                return Err(RessourceError::data_too_large(&target, limit));
+            }
+
+            self.write_metadata_file(&target, &import.metadata_bytes)
+                .await
+                .map_err(|e| RessourceError::write_metadata_io(e, &target))?;
+
+            let written = if is_folder {
+                match self.backend.is_dir(&data_path).await {
+                    Ok(true) => Ok(()),
+                    Ok(false) => self.backend.create_dir(&data_path).await,
+                    Err(e) => Err(e),
+                }
+            } else {
+                RetryBackend::new(&self.backend, self.retry)
+                    .rename(&data.spooled, &data_path)
+                    .await
+            };
+
+            written.map_err(|e| RessourceError::write_data_io(e, &target, &data_path))?;
+
+            self.journal(if exists {
+                JournalOperation::Update { path: target }
+            } else {
+                JournalOperation::Create { path: target }
+            })
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod tests {
+    use super::ConflictPolicy;
+    use crate::{
+        error::RessourceError, folder_ressource::FolderRessource, memory_backend::MemoryBackend,
+        meta::RessourceMetadata, ressource::Ressource, ressources::Ressources,
+        text_ressource::TextRessource,
+    };
+    use futures::executor::block_on;
+    use tokio_tar::{Builder, Header};
+
+    async fn setup() -> (Ressources<MemoryBackend>, Vec<u8>) {
+        let ressources = Ressources::init_with_backend("/vault".into(), MemoryBackend::new())
+            .await
+            .unwrap();
+        for id in ["src", "dst"] {
+            Ressource::new(
+                &ressources,
+                ressources.path(vec![id.into()]),
+                FolderRessource::new(),
+            )
+            .await
+            .unwrap();
+        }
+        let src = ressources.path(vec!["src".into()]);
+        for (id, text) in [("a", "archived"), ("b", "new")] {
+            Ressource::new(
+                &ressources,
+                src.with_child(id),
+                TextRessource::new(text.into()),
+            )
+            .await
+            .unwrap();
+        }
+        Ressource::new(
+            &ressources,
+            ressources.path(vec!["dst".into(), "a".into()]),
+            TextRessource::new("existing".into()),
+        )
+        .await
+        .unwrap();
+
+        let mut archive = Vec::new();
+        ressources.export_tar(src, &mut archive).await.unwrap();
+        (ressources, archive)
+    }
+
+    async fn text(ressources: &Ressources<MemoryBackend>, id: &str) -> Option<String> {
+        let path = ressources.path(vec!["dst".into(), id.into()]);
+        Ressource::<TextRessource>::load(ressources, path)
+            .await
+            .ok()
+            .map(|ressource| ressource.data.text)
+    }
+
+    async fn tar(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = Builder::new_non_terminated(Vec::new());
+        for (path, data) in entries {
+            let mut header = Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            builder.append_data(&mut header, path, *data).await.unwrap();
+        }
+        builder.into_inner().await.unwrap()
+    }
+
+    #[test]
+    fn import_applies_conflict_policy() {
+        block_on(async {
+            let (ressources, archive) = setup().await;
+            let dst = ressources.path(vec!["dst".into()]);
+
+            assert!(matches!(
+                ressources
+                    .import_tar(dst.clone(), archive.as_slice(), ConflictPolicy::Error)
+                    .await,
+                Err(RessourceError::RessourceExists { .. })
+            ));
+            assert_eq!(text(&ressources, "a").await.as_deref(), Some("existing"));
+
+            ressources
+                .import_tar(dst.clone(), archive.as_slice(), ConflictPolicy::Skip)
+                .await
+                .unwrap();
+            assert_eq!(text(&ressources, "a").await.as_deref(), Some("existing"));
+            assert_eq!(text(&ressources, "b").await.as_deref(), Some("new"));
+
+            ressources
+                .import_tar(dst, archive.as_slice(), ConflictPolicy::Overwrite)
+                .await
+                .unwrap();
+            assert_eq!(text(&ressources, "a").await.as_deref(), Some("archived"));
+            assert_eq!(text(&ressources, "b").await.as_deref(), Some("new"));
+        });
+    }
+
+    #[test]
+    fn import_rejects_reserved_ids_and_bad_extensions() {
+        block_on(async {
+            let (ressources, _) = setup().await;
+            let dst = ressources.path(vec!["dst".into()]);
+
+            let reserved = tar(&[("c.data.meta.json", b"{}")]).await;
+            assert!(matches!(
+                ressources
+                    .import_tar(dst.clone(), reserved.as_slice(), ConflictPolicy::Error)
+                    .await,
+                Err(RessourceError::ReservedIdSuffix { .. })
+            ));
+
+            let mut metadata = RessourceMetadata::load(
+                &ressources,
+                &ressources.path(vec!["src".into(), "a".into()]),
+            )
+            .await
+            .unwrap();
+            metadata.id = "c".into();
+            metadata.data_extension = "../../escape".into();
+            let metadata = serde_json::to_vec(&metadata).unwrap();
+            let escaping = tar(&[("c.meta.json", &metadata)]).await;
+            assert!(matches!(
+                ressources
+                    .import_tar(dst, escaping.as_slice(), ConflictPolicy::Error)
+                    .await,
+                Err(RessourceError::InvalidArchiveEntry { .. })
+            ));
+            assert_eq!(text(&ressources, "c").await, None);
+        });
+    }
+}
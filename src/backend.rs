@@ -0,0 +1,559 @@
+use futures::{Stream, stream};
+use std::{
+    ffi::OsString,
+    io::{self, SeekFrom},
+    path::Path,
+    pin::Pin,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    fs,
+    io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt},
+};
+
+pub trait Backend: Send + Sync {
+    type ReadDir: Stream<Item = io::Result<OsString>> + Send + Unpin;
+
+    fn read(&self, path: &Path) -> impl Future<Output = io::Result<Vec<u8>>> + Send;
+    fn read_range(
+        &self,
+        path: &Path,
+        start: u64,
+        len: u64,
+    ) -> impl Future<Output = io::Result<Vec<u8>>> + Send {
+        async move {
+            let data = self.read(path).await?;
+            let start = usize::try_from(start).unwrap_or(usize::MAX).min(data.len());
+            let end = usize::try_from(len)
+                .unwrap_or(usize::MAX)
+                .saturating_add(start)
+                .min(data.len());
+            Ok(data[start..end].to_vec())
+        }
+    }
+    fn write(&self, path: &Path, data: &[u8]) -> impl Future<Output = io::Result<()>> + Send;
+    fn create_new(&self, path: &Path, data: &[u8]) -> impl Future<Output = io::Result<()>> + Send {
+        async move {
+            if self.exists(path).await? {
+                return Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    format!("File exists: {}", path.display()),
+                ));
+            }
+            self.write(path, data).await
+        }
+    }
+    fn append(&self, path: &Path, data: &[u8]) -> impl Future<Output = io::Result<()>> + Send {
+        async move {
+            let mut existing = match self.read(path).await {
+                Ok(existing) => existing,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+                Err(e) => return Err(e),
+            };
+            existing.extend_from_slice(data);
+            self.write(path, &existing).await
+        }
+    }
+    fn remove_file(&self, path: &Path) -> impl Future<Output = io::Result<()>> + Send;
+    fn read_dir(&self, path: &Path) -> impl Future<Output = io::Result<Self::ReadDir>> + Send;
+    fn create_dir(&self, path: &Path) -> impl Future<Output = io::Result<()>> + Send;
+    fn remove_dir(&self, path: &Path) -> impl Future<Output = io::Result<()>> + Send;
+    fn rename(&self, from: &Path, to: &Path) -> impl Future<Output = io::Result<()>> + Send;
+    fn hard_link(
+        &self,
+        _original: &Path,
+        link: &Path,
+    ) -> impl Future<Output = io::Result<()>> + Send {
+        async move {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("Hard links are not supported. Path: {}", link.display()),
+            ))
+        }
+    }
+    #[cfg(feature = "mmap")]
+    fn map(&self, path: &Path) -> impl Future<Output = io::Result<memmap2::Mmap>> + Send {
+        async move { map_bytes(&self.read(path).await?) }
+    }
+    fn len(&self, path: &Path) -> impl Future<Output = io::Result<u64>> + Send {
+        async move { Ok(self.read(path).await?.len() as u64) }
+    }
+    fn link_count(&self, _path: &Path) -> impl Future<Output = io::Result<u64>> + Send {
+        async move { Ok(1) }
+    }
+    fn same_file(&self, _a: &Path, _b: &Path) -> impl Future<Output = io::Result<bool>> + Send {
+        async move { Ok(false) }
+    }
+    fn is_dir(&self, path: &Path) -> impl Future<Output = io::Result<bool>> + Send;
+    fn exists(&self, path: &Path) -> impl Future<Output = io::Result<bool>> + Send;
+    fn sync(&self, path: &Path) -> impl Future<Output = io::Result<()>> + Send;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FsBackend;
+
+#[cfg(feature = "mmap")]
+pub fn map_bytes(data: &[u8]) -> io::Result<memmap2::Mmap> {
+    let mut map = memmap2::MmapMut::map_anon(data.len())?;
+    map.copy_from_slice(data);
+    map.make_read_only()
+}
+
+pub struct FsReadDir(fs::ReadDir);
+
+impl Stream for FsReadDir {
+    type Item = io::Result<OsString>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0
+            .poll_next_entry(cx)
+            .map(|entry| entry.transpose().map(|e| e.map(|entry| entry.file_name())))
+    }
+}
+
+impl Backend for FsBackend {
+    type ReadDir = FsReadDir;
+
+    async fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        fs::read(path).await
+    }
+
+    async fn read_range(&self, path: &Path, start: u64, len: u64) -> io::Result<Vec<u8>> {
+        let mut file = fs::File::open(path).await?;
+        file.seek(SeekFrom::Start(start)).await?;
+        let mut data = Vec::new();
+        file.take(len).read_to_end(&mut data).await?;
+        Ok(data)
+    }
+
+    async fn write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        fs::write(path, data).await
+    }
+
+    async fn create_new(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .await?
+            .write_all(data)
+            .await
+    }
+
+    async fn append(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)
+            .await?
+            .write_all(data)
+            .await
+    }
+
+    async fn remove_file(&self, path: &Path) -> io::Result<()> {
+        fs::remove_file(path).await
+    }
+
+    async fn read_dir(&self, path: &Path) -> io::Result<FsReadDir> {
+        fs::read_dir(path).await.map(FsReadDir)
+    }
+
+    async fn create_dir(&self, path: &Path) -> io::Result<()> {
+        fs::create_dir(path).await
+    }
+
+    async fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        fs::remove_dir(path).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        fs::rename(from, to).await
+    }
+
+    async fn hard_link(&self, original: &Path, link: &Path) -> io::Result<()> {
+        fs::hard_link(original, link).await
+    }
+
+    #[cfg(feature = "mmap")]
+    async fn map(&self, path: &Path) -> io::Result<memmap2::Mmap> {
+        let file = fs::File::open(path).await?.into_std().await;
+        // SAFETY: the map is only sound while no one truncates or rewrites the file.
+        // Ressource writes replace the data file instead of writing in place, but
+        // external writers can still change the mapped bytes under a reader.
+        unsafe { memmap2::Mmap::map(&file) }
+    }
+
+    async fn is_dir(&self, path: &Path) -> io::Result<bool> {
+        match fs::metadata(path).await {
+            Ok(metadata) => Ok(metadata.is_dir()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn len(&self, path: &Path) -> io::Result<u64> {
+        Ok(fs::metadata(path).await?.len())
+    }
+
+    #[cfg(unix)]
+    async fn link_count(&self, path: &Path) -> io::Result<u64> {
+        use std::os::unix::fs::MetadataExt;
+        Ok(fs::metadata(path).await?.nlink())
+    }
+
+    #[cfg(unix)]
+    async fn same_file(&self, a: &Path, b: &Path) -> io::Result<bool> {
+        use std::os::unix::fs::MetadataExt;
+        let (a, b) = (fs::metadata(a).await?, fs::metadata(b).await?);
+        Ok(a.dev() == b.dev() && a.ino() == b.ino())
+    }
+
+    async fn exists(&self, path: &Path) -> io::Result<bool> {
+        fs::try_exists(path).await
+    }
+
+    async fn sync(&self, path: &Path) -> io::Result<()> {
+        fs::File::open(path).await?.sync_all().await
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BufferBackend {
+    data: Mutex<Option<Vec<u8>>>,
+}
+
+impl BufferBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_data(data: Vec<u8>) -> Self {
+        BufferBackend {
+            data: Mutex::new(Some(data)),
+        }
+    }
+
+    pub fn into_data(self) -> Option<Vec<u8>> {
+        self.data.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn data(&self) -> std::sync::MutexGuard<'_, Option<Vec<u8>>> {
+        self.data.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn unsupported(operation: &str, path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!(
+            "BufferBackend: {operation} is not supported. Path: {}",
+            path.display()
+        ),
+    )
+}
+
+impl Backend for BufferBackend {
+    type ReadDir = stream::Empty<io::Result<OsString>>;
+
+    async fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.data().clone().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("BufferBackend: No data buffered for {}", path.display()),
+            )
+        })
+    }
+
+    async fn write(&self, _path: &Path, data: &[u8]) -> io::Result<()> {
+        *self.data() = Some(data.to_vec());
+        Ok(())
+    }
+
+    async fn remove_file(&self, _path: &Path) -> io::Result<()> {
+        *self.data() = None;
+        Ok(())
+    }
+
+    async fn read_dir(&self, path: &Path) -> io::Result<Self::ReadDir> {
+        Err(unsupported("read_dir", path))
+    }
+
+    async fn create_dir(&self, path: &Path) -> io::Result<()> {
+        Err(unsupported("create_dir", path))
+    }
+
+    async fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        Err(unsupported("remove_dir", path))
+    }
+
+    async fn rename(&self, from: &Path, _to: &Path) -> io::Result<()> {
+        Err(unsupported("rename", from))
+    }
+
+    async fn is_dir(&self, _path: &Path) -> io::Result<bool> {
+        Ok(false)
+    }
+
+    async fn exists(&self, _path: &Path) -> io::Result<bool> {
+        Ok(self.data().is_some())
+    }
+
+    async fn sync(&self, _path: &Path) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct LimitedBackend<'a, B: Backend> {
+    inner: &'a B,
+    limit: u64,
+    written: AtomicU64,
+    exceeded: AtomicBool,
+}
+
+impl<'a, B: Backend> LimitedBackend<'a, B> {
+    pub fn new(inner: &'a B, limit: u64) -> Self {
+        LimitedBackend {
+            inner,
+            limit,
+            written: AtomicU64::new(0),
+            exceeded: AtomicBool::new(false),
+        }
+    }
+
+    pub fn exceeded(&self) -> bool {
+        self.exceeded.load(Ordering::SeqCst)
+    }
+
+    fn check(&self, written: u64, path: &Path) -> io::Result<()> {
+        if written <= self.limit {
+            return Ok(());
+        }
+
+        self.exceeded.store(true, Ordering::SeqCst);
+        Err(io::Error::new(
+            io::ErrorKind::FileTooLarge,
+            format!(
+                "LimitedBackend: Data exceeds the limit of {} bytes. Path: {}",
+                self.limit,
+                path.display()
+            ),
+        ))
+    }
+}
+
+impl<B: Backend> Backend for LimitedBackend<'_, B> {
+    type ReadDir = B::ReadDir;
+
+    async fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.inner.read(path).await
+    }
+
+    async fn read_range(&self, path: &Path, start: u64, len: u64) -> io::Result<Vec<u8>> {
+        self.inner.read_range(path, start, len).await
+    }
+
+    async fn write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        let written = data.len() as u64;
+        self.check(written, path)?;
+        self.inner.write(path, data).await?;
+        self.written.store(written, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn create_new(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        let written = data.len() as u64;
+        self.check(written, path)?;
+        self.inner.create_new(path, data).await?;
+        self.written.store(written, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn append(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        let written = self.written.load(Ordering::SeqCst) + data.len() as u64;
+        self.check(written, path)?;
+        self.inner.append(path, data).await?;
+        self.written.store(written, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn remove_file(&self, path: &Path) -> io::Result<()> {
+        self.inner.remove_file(path).await
+    }
+
+    async fn read_dir(&self, path: &Path) -> io::Result<Self::ReadDir> {
+        self.inner.read_dir(path).await
+    }
+
+    async fn create_dir(&self, path: &Path) -> io::Result<()> {
+        self.inner.create_dir(path).await
+    }
+
+    async fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        self.inner.remove_dir(path).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        self.inner.rename(from, to).await
+    }
+
+    async fn hard_link(&self, original: &Path, link: &Path) -> io::Result<()> {
+        self.inner.hard_link(original, link).await
+    }
+
+    #[cfg(feature = "mmap")]
+    async fn map(&self, path: &Path) -> io::Result<memmap2::Mmap> {
+        self.inner.map(path).await
+    }
+
+    async fn is_dir(&self, path: &Path) -> io::Result<bool> {
+        self.inner.is_dir(path).await
+    }
+
+    async fn len(&self, path: &Path) -> io::Result<u64> {
+        self.inner.len(path).await
+    }
+
+    async fn link_count(&self, path: &Path) -> io::Result<u64> {
+        self.inner.link_count(path).await
+    }
+
+    async fn same_file(&self, a: &Path, b: &Path) -> io::Result<bool> {
+        self.inner.same_file(a, b).await
+    }
+
+    async fn exists(&self, path: &Path) -> io::Result<bool> {
+        self.inner.exists(path).await
+    }
+
+    async fn sync(&self, path: &Path) -> io::Result<()> {
+        self.inner.sync(path).await
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub fn is_transient(error: &io::Error) -> bool {
+        matches!(
+            error.kind(),
+            io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct RetryBackend<'a, B: Backend> {
+    inner: &'a B,
+    policy: Option<RetryPolicy>,
+}
+
+impl<'a, B: Backend> RetryBackend<'a, B> {
+    pub fn new(inner: &'a B, policy: Option<RetryPolicy>) -> Self {
+        RetryBackend { inner, policy }
+    }
+
+    async fn retry<T, F: Future<Output = io::Result<T>>>(
+        &self,
+        mut operation: impl FnMut() -> F,
+    ) -> io::Result<T> {
+        let mut attempt = 0;
+        loop {
+            match (operation().await, self.policy) {
+                (Err(e), Some(policy))
+                    if attempt < policy.max_retries && RetryPolicy::is_transient(&e) =>
+                {
+                    tokio::time::sleep(policy.backoff.saturating_mul(1 << attempt.min(16))).await;
+                    attempt += 1;
+                }
+                (result, _) => return result,
+            }
+        }
+    }
+}
+
+impl<B: Backend> Backend for RetryBackend<'_, B> {
+    type ReadDir = B::ReadDir;
+
+    async fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.inner.read(path).await
+    }
+
+    async fn read_range(&self, path: &Path, start: u64, len: u64) -> io::Result<Vec<u8>> {
+        self.inner.read_range(path, start, len).await
+    }
+
+    async fn write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        self.retry(|| self.inner.write(path, data)).await
+    }
+
+    async fn create_new(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        self.inner.create_new(path, data).await
+    }
+
+    async fn append(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        self.retry(|| self.inner.append(path, data)).await
+    }
+
+    async fn remove_file(&self, path: &Path) -> io::Result<()> {
+        self.inner.remove_file(path).await
+    }
+
+    async fn read_dir(&self, path: &Path) -> io::Result<Self::ReadDir> {
+        self.inner.read_dir(path).await
+    }
+
+    async fn create_dir(&self, path: &Path) -> io::Result<()> {
+        self.retry(|| self.inner.create_dir(path)).await
+    }
+
+    async fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        self.inner.remove_dir(path).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        self.inner.rename(from, to).await
+    }
+
+    async fn hard_link(&self, original: &Path, link: &Path) -> io::Result<()> {
+        self.inner.hard_link(original, link).await
+    }
+
+    #[cfg(feature = "mmap")]
+    async fn map(&self, path: &Path) -> io::Result<memmap2::Mmap> {
+        self.inner.map(path).await
+    }
+
+    async fn is_dir(&self, path: &Path) -> io::Result<bool> {
+        self.inner.is_dir(path).await
+    }
+
+    async fn len(&self, path: &Path) -> io::Result<u64> {
+        self.inner.len(path).await
+    }
+
+    async fn link_count(&self, path: &Path) -> io::Result<u64> {
+        self.inner.link_count(path).await
+    }
+
+    async fn same_file(&self, a: &Path, b: &Path) -> io::Result<bool> {
+        self.inner.same_file(a, b).await
+    }
+
+    async fn exists(&self, path: &Path) -> io::Result<bool> {
+        self.inner.exists(path).await
+    }
+
+    async fn sync(&self, path: &Path) -> io::Result<()> {
+        self.inner.sync(path).await
+    }
+}
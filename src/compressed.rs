@@ -0,0 +1,163 @@
+use crate::{
+    backend::{Backend, BufferBackend},
+    intern::intern_joined,
+    traits::{ReadableRessource, RessourceType, WritableRessource},
+};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use std::{
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum CompressedRessourceError<E: std::error::Error> {
+    #[error("CompressedRessource: IO Error reading data at {path}. Error: {error}")]
+    Reading {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+
+    #[error("CompressedRessource: IO Error writing data at {path}. Error: {error}")]
+    Writing {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+
+    #[error("CompressedRessource: Unable to compress data for {path}. Error: {error}")]
+    Compress {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+
+    #[error("CompressedRessource: Unable to decompress data at {path}. Error: {error}")]
+    Decompress {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+
+    #[error("CompressedRessource: Inner ressource did not write any data for {path}")]
+    NoData { path: PathBuf },
+
+    #[error("CompressedRessource: Inner ressource error at {path}. Error: {error}")]
+    Inner { path: PathBuf, error: E },
+}
+
+#[derive(Debug, Clone)]
+pub struct Compressed<T> {
+    pub inner: T,
+}
+
+impl<T> Compressed<T> {
+    pub fn new(inner: T) -> Self {
+        Compressed { inner }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: RessourceType> RessourceType for Compressed<T> {
+    fn id() -> &'static str {
+        intern_joined(T::id(), '+', "gzip")
+    }
+
+    fn display_name() -> &'static str {
+        T::display_name()
+    }
+}
+
+impl<T: ReadableRessource + Send> ReadableRessource for Compressed<T> {
+    type Error = CompressedRessourceError<T::Error>;
+    async fn read<B: Backend>(backend: &B, path: &Path) -> Result<Self, Self::Error> {
+        read_gzip(backend, path).await.map(Compressed::new)
+    }
+}
+
+pub(crate) fn gunzip(compressed: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    GzDecoder::new(compressed).read_to_end(&mut data)?;
+    Ok(data)
+}
+
+pub(crate) async fn read_gzip<T: ReadableRessource, B: Backend>(
+    backend: &B,
+    path: &Path,
+) -> Result<T, CompressedRessourceError<T::Error>> {
+    let compressed = backend
+        .read(path)
+        .await
+        .map_err(|e| CompressedRessourceError::Reading {
+            path: path.to_path_buf(),
+            error: e,
+        })?;
+
+    let data = gunzip(&compressed).map_err(|e| CompressedRessourceError::Decompress {
+        path: path.to_path_buf(),
+        error: e,
+    })?;
+
+    T::read(&BufferBackend::with_data(data), path)
+        .await
+        .map_err(|e| CompressedRessourceError::Inner {
+            path: path.to_path_buf(),
+            error: e,
+        })
+}
+
+impl<T: WritableRessource + Sync> WritableRessource for Compressed<T> {
+    type Error = CompressedRessourceError<T::Error>;
+    async fn write<B: Backend>(&self, backend: &B, path: &Path) -> Result<(), Self::Error> {
+        let buffer = BufferBackend::new();
+        self.inner
+            .write(&buffer, path)
+            .await
+            .map_err(|e| CompressedRessourceError::Inner {
+                path: path.to_path_buf(),
+                error: e,
+            })?;
+
+        let data = buffer
+            .into_data()
+            .ok_or_else(|| CompressedRessourceError::NoData {
+                path: path.to_path_buf(),
+            })?;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        let compressed = encoder
+            .write_all(&data)
+            .and_then(|_| encoder.finish())
+            .map_err(|e| CompressedRessourceError::Compress {
+                path: path.to_path_buf(),
+                error: e,
+            })?;
+
+        backend
+            .write(path, &compressed)
+            .await
+            .map_err(|e| CompressedRessourceError::Writing {
+                path: path.to_path_buf(),
+                error: e,
+            })
+    }
+
+    fn data_extension() -> &'static str {
+        match T::data_extension() {
+            "" => "gz",
+            extension => intern_joined(extension, '.', "gz"),
+        }
+    }
+
+    fn extra_metadata() -> serde_json::Map<String, serde_json::Value> {
+        T::extra_metadata()
+    }
+
+    fn mime_type() -> Option<&'static str> {
+        T::mime_type()
+    }
+
+    fn mime(&self) -> Option<&str> {
+        self.inner.mime()
+    }
+}
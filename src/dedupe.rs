@@ -0,0 +1,147 @@
+use crate::{
+    backend::Backend,
+    error::{RessourceError, RessourceResult},
+    folder_ressource::FolderRessource,
+    path::RessourcePath,
+    ressources::Ressources,
+    traits::RessourceType,
+};
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::PathBuf,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct DedupeReport {
+    pub linked: Vec<RessourcePath>,
+    pub saved_bytes: u64,
+}
+
+impl<B: Backend> Ressources<B> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            err,
+            fields(path = %path.resolve().display())
+        )
+    )]
+    pub async fn dedupe(&self, path: RessourcePath) -> RessourceResult<DedupeReport> {
+        self.check_writable(&path)?;
+
+        let mut originals: HashMap<(usize, u64), Vec<PathBuf>> = HashMap::new();
+        let mut report = DedupeReport::default();
+
+        for (entry, metadata) in self.walk(path).await? {
+            if metadata.type_id == FolderRessource::id() {
+                continue;
+            }
+
+            let data_path = metadata.data_path(&entry);
+            let read_error = |e, path: &PathBuf| RessourceError::read_data_io(e, &entry, path);
+            let data = self
+                .backend
+                .read(&data_path)
+                .await
+                .map_err(|e| read_error(e, &data_path))?;
+
+            let mut hasher = DefaultHasher::new();
+            data.hash(&mut hasher);
+            let candidates = originals.entry((data.len(), hasher.finish())).or_default();
+
+            let mut original = None;
+            let mut already_linked = false;
+            for candidate in candidates.iter() {
+                if self
+                    .backend
+                    .same_file(candidate, &data_path)
+                    .await
+                    .map_err(|e| read_error(e, candidate))?
+                {
+                    already_linked = true;
+                    break;
+                }
+
+                let candidate_data = self
+                    .backend
+                    .read(candidate)
+                    .await
+                    .map_err(|e| read_error(e, candidate))?;
+                if candidate_data == data {
+                    original = Some(candidate.clone());
+                    break;
+                }
+            }
+
+            if already_linked {
+                continue;
+            }
+            let Some(original) = original else {
+                candidates.push(data_path);
+                continue;
+            };
+
+            let freed = self
+                .backend
+                .link_count(&data_path)
+                .await
+                .map_err(|e| read_error(e, &data_path))?
+                == 1;
+
+            let link_path = data_path.with_added_extension("dedupe");
+            let linked = match self.backend.hard_link(&original, &link_path).await {
+                Ok(()) => self.backend.rename(&link_path, &data_path).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = linked {
+                let _ = self.backend.remove_file(&link_path).await;
+                return Err(RessourceError::write_data_io(e, &entry, &data_path));
+            }
+
+            if freed {
+                report.saved_bytes += data.len() as u64;
+            }
+            report.linked.push(entry);
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod tests {
+    use crate::{
+        memory_backend::MemoryBackend, ressource::Ressource, ressources::Ressources,
+        text_ressource::TextRessource,
+    };
+    use futures::executor::block_on;
+
+    #[test]
+    fn second_run_finds_nothing_to_link() {
+        block_on(async {
+            let ressources = Ressources::init_with_backend("/vault".into(), MemoryBackend::new())
+                .await
+                .unwrap();
+            for id in ["a", "b", "c"] {
+                Ressource::new(
+                    &ressources,
+                    ressources.path(vec![id.into()]),
+                    TextRessource::new("same".into()),
+                )
+                .await
+                .unwrap();
+            }
+
+            let root = ressources.path(Vec::new());
+            let first = ressources.dedupe(root.clone()).await.unwrap();
+            assert_eq!(first.linked.len(), 2);
+            assert_eq!(first.saved_bytes, 8);
+
+            let second = ressources.dedupe(root).await.unwrap();
+            assert!(second.linked.is_empty());
+            assert_eq!(second.saved_bytes, 0);
+        });
+    }
+}
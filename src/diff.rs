@@ -0,0 +1,95 @@
+use crate::{
+    backend::Backend,
+    error::RessourceResult,
+    meta::RessourceMetadata,
+    path::{RessourceId, RessourcePath},
+    ressources::Ressources,
+};
+use std::{collections::BTreeMap, path::PathBuf};
+
+#[derive(Debug, Clone)]
+pub enum DiffEntry {
+    Added {
+        path: RessourcePath,
+        metadata: RessourceMetadata,
+    },
+    Removed {
+        path: RessourcePath,
+        metadata: RessourceMetadata,
+    },
+    Changed {
+        path: RessourcePath,
+        before: RessourceMetadata,
+        after: RessourceMetadata,
+    },
+}
+
+impl DiffEntry {
+    pub fn path(&self) -> &RessourcePath {
+        match self {
+            DiffEntry::Added { path, .. }
+            | DiffEntry::Removed { path, .. }
+            | DiffEntry::Changed { path, .. } => path,
+        }
+    }
+}
+
+fn is_changed(before: &RessourceMetadata, after: &RessourceMetadata) -> bool {
+    before.type_id != after.type_id
+        || before.data_extension != after.data_extension
+        || before.last_modified() != after.last_modified()
+        || before.extra != after.extra
+}
+
+impl<B: Backend> Ressources<B> {
+    async fn relative_entries(
+        &self,
+        path: RessourcePath,
+    ) -> RessourceResult<BTreeMap<Vec<RessourceId>, RessourceMetadata>> {
+        let depth = path.path.len();
+        Ok(self
+            .walk(path)
+            .await?
+            .into_iter()
+            .map(|(entry, metadata)| (entry.path[depth..].to_vec(), metadata))
+            .collect())
+    }
+
+    pub async fn diff(
+        &self,
+        a: RessourcePath,
+        b: RessourcePath,
+    ) -> RessourceResult<Vec<DiffEntry>> {
+        let mut before = self.relative_entries(a).await?;
+        let after = self.relative_entries(b).await?;
+        let mut diff = Vec::new();
+
+        for (ids, after) in after {
+            let path = RessourcePath::from_vec(PathBuf::new(), ids.clone());
+            match before.remove(&ids) {
+                None => diff.push(DiffEntry::Added {
+                    path,
+                    metadata: after,
+                }),
+                Some(before) if is_changed(&before, &after) => diff.push(DiffEntry::Changed {
+                    path,
+                    before,
+                    after,
+                }),
+                Some(_) => {}
+            }
+        }
+
+        diff.extend(
+            before
+                .into_iter()
+                .map(|(ids, metadata)| DiffEntry::Removed {
+                    path: RessourcePath::from_vec(PathBuf::new(), ids),
+                    metadata,
+                }),
+        );
+        diff.sort_by(|a, b| a.path().path.cmp(&b.path().path));
+
+        Ok(diff)
+    }
+}
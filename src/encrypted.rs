@@ -0,0 +1,201 @@
+use crate::{
+    backend::{Backend, BufferBackend},
+    error::{RessourceError, RessourceResult},
+    intern::intern_joined,
+    meta::MetaRessource,
+    path::RessourcePath,
+    ressource::Ressource,
+    ressources::Ressources,
+    traits::{ReadableRessource, RessourceType, WritableRessource},
+};
+use aes_gcm::{
+    Aes256Gcm, Key, Nonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+const NONCE_LENGTH: usize = 12;
+
+pub type EncryptionKey = [u8; 32];
+
+#[derive(Error, Debug)]
+pub enum EncryptedRessourceError<E: std::error::Error> {
+    #[error("EncryptedRessource: IO Error reading data at {path}. Error: {error}")]
+    Reading {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+
+    #[error("EncryptedRessource: IO Error writing data at {path}. Error: {error}")]
+    Writing {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+
+    #[error("EncryptedRessource: Unable to encrypt data for {path}")]
+    Encrypt { path: PathBuf },
+
+    #[error(
+        "EncryptedRessource: Unable to decrypt data at {path}. The key is wrong or the data was tampered with"
+    )]
+    Decrypt { path: PathBuf },
+
+    #[error("EncryptedRessource: Data at {path} is too short to contain a nonce")]
+    MissingNonce { path: PathBuf },
+
+    #[error("EncryptedRessource: Inner ressource did not write any data for {path}")]
+    NoData { path: PathBuf },
+
+    #[error("EncryptedRessource: Inner ressource error at {path}. Error: {error}")]
+    Inner { path: PathBuf, error: E },
+}
+
+#[derive(Clone)]
+pub struct Encrypted<T> {
+    pub inner: T,
+    key: EncryptionKey,
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Encrypted<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Encrypted")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Encrypted<T> {
+    pub fn new(inner: T, key: EncryptionKey) -> Self {
+        Encrypted { inner, key }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: ReadableRessource + Send> Encrypted<T> {
+    pub async fn read<B: Backend>(
+        backend: &B,
+        path: &Path,
+        key: EncryptionKey,
+    ) -> Result<Self, EncryptedRessourceError<T::Error>> {
+        let encrypted = backend
+            .read(path)
+            .await
+            .map_err(|e| EncryptedRessourceError::Reading {
+                path: path.to_path_buf(),
+                error: e,
+            })?;
+
+        if encrypted.len() < NONCE_LENGTH {
+            return Err(EncryptedRessourceError::MissingNonce {
+                path: path.to_path_buf(),
+            });
+        }
+
+        let (nonce, ciphertext) = encrypted.split_at(NONCE_LENGTH);
+        let data = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| EncryptedRessourceError::Decrypt {
+                path: path.to_path_buf(),
+            })?;
+
+        let inner = T::read(&BufferBackend::with_data(data), path)
+            .await
+            .map_err(|e| EncryptedRessourceError::Inner {
+                path: path.to_path_buf(),
+                error: e,
+            })?;
+
+        Ok(Encrypted { inner, key })
+    }
+}
+
+impl<T: ReadableRessource + Send> Ressource<Encrypted<T>> {
+    pub async fn load_encrypted<B: Backend>(
+        ressources: &Ressources<B>,
+        path: RessourcePath,
+        key: EncryptionKey,
+    ) -> RessourceResult<Self> {
+        let meta_ressource = MetaRessource::<Encrypted<T>>::load(ressources, path.clone()).await?;
+        let data = Encrypted::<T>::read(&ressources.backend, &meta_ressource.data_path(), key)
+            .await
+            .map_err(|e| RessourceError::invalid_data(Encrypted::<T>::id(), &path, Box::new(e)))?;
+
+        Ok(Ressource {
+            data,
+            meta: meta_ressource,
+        })
+    }
+}
+
+impl<T: RessourceType> RessourceType for Encrypted<T> {
+    fn id() -> &'static str {
+        intern_joined(T::id(), '+', "aes-gcm")
+    }
+
+    fn display_name() -> &'static str {
+        T::display_name()
+    }
+}
+
+impl<T: WritableRessource + Sync> WritableRessource for Encrypted<T> {
+    type Error = EncryptedRessourceError<T::Error>;
+    async fn write<B: Backend>(&self, backend: &B, path: &Path) -> Result<(), Self::Error> {
+        let buffer = BufferBackend::new();
+        self.inner
+            .write(&buffer, path)
+            .await
+            .map_err(|e| EncryptedRessourceError::Inner {
+                path: path.to_path_buf(),
+                error: e,
+            })?;
+
+        let data = buffer
+            .into_data()
+            .ok_or_else(|| EncryptedRessourceError::NoData {
+                path: path.to_path_buf(),
+            })?;
+
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.key))
+            .encrypt(&nonce, data.as_slice())
+            .map_err(|_| EncryptedRessourceError::Encrypt {
+                path: path.to_path_buf(),
+            })?;
+
+        let mut encrypted = nonce.to_vec();
+        encrypted.extend(ciphertext);
+
+        backend
+            .write(path, &encrypted)
+            .await
+            .map_err(|e| EncryptedRessourceError::Writing {
+                path: path.to_path_buf(),
+                error: e,
+            })
+    }
+
+    fn data_extension() -> &'static str {
+        match T::data_extension() {
+            "" => "enc",
+            extension => intern_joined(extension, '.', "enc"),
+        }
+    }
+
+    fn extra_metadata() -> serde_json::Map<String, serde_json::Value> {
+        let mut extra = T::extra_metadata();
+        extra.insert("encrypted".to_string(), serde_json::Value::Bool(true));
+        extra
+    }
+
+    fn mime_type() -> Option<&'static str> {
+        T::mime_type()
+    }
+
+    fn mime(&self) -> Option<&str> {
+        self.inner.mime()
+    }
+}
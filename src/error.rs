@@ -1,5 +1,8 @@
-use crate::path::RessourcePath;
-use std::{fmt::Debug, path::PathBuf};
+use crate::path::{RessourceId, RessourcePath};
+use std::{
+    fmt::Debug,
+    path::{Path, PathBuf},
+};
 use thiserror::Error;
 
 #[derive(Debug)]
@@ -10,6 +13,21 @@ pub struct WriteDataError {
     pub error: Box<dyn std::error::Error>,
 }
 
+impl WriteDataError {
+    pub fn new(
+        ressource_type: &'static str,
+        path: &RessourcePath,
+        error: impl std::error::Error + 'static,
+    ) -> Self {
+        WriteDataError {
+            ressource_type,
+            ressource_path: path.clone(),
+            path: path.resolve(),
+            error: Box::new(error),
+        }
+    }
+}
+
 impl std::fmt::Display for WriteDataError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -45,6 +63,15 @@ pub enum RessourceError {
         path: PathBuf,
     },
 
+    #[error(
+        "Unable to serialize metadata for ressource at: {ressource_path}. OSPath: {path}. Error: {error}"
+    )]
+    MetadataSerialize {
+        error: serde_json::Error,
+        ressource_path: RessourcePath,
+        path: PathBuf,
+    },
+
     #[error(
         "Malformed metadata for ressource at: {ressource_path}. OSPath: {path}. Error: {error}"
     )]
@@ -54,6 +81,49 @@ pub enum RessourceError {
         path: PathBuf,
     },
 
+    #[error("Unable to convert metadata from or to bytes. Error: {error}")]
+    MetadataBytes { error: serde_json::Error },
+
+    #[error(
+        "IO Error reading data for ressource at: {ressource_path}. OSPath: {path}. Error: {error}"
+    )]
+    ReadDataIO {
+        error: std::io::Error,
+        ressource_path: RessourcePath,
+        path: PathBuf,
+    },
+
+    #[error("Archive Error for ressource at: {ressource_path}. OSPath: {path}. Error: {error}")]
+    Archive {
+        error: std::io::Error,
+        ressource_path: RessourcePath,
+        path: PathBuf,
+    },
+
+    #[error("Invalid archive entry {entry} while importing into ressource at: {ressource_path}")]
+    InvalidArchiveEntry {
+        ressource_path: RessourcePath,
+        entry: PathBuf,
+    },
+
+    #[error(
+        "Archive entry {entry} for ressource at: {ressource_path} has no matching data entry. Expected: {expected}"
+    )]
+    ArchiveDataMismatch {
+        ressource_path: RessourcePath,
+        entry: PathBuf,
+        expected: PathBuf,
+    },
+
+    #[error(
+        "IO Error writing data for ressource at: {ressource_path}. OSPath: {path}. Error: {error}"
+    )]
+    WriteDataIO {
+        error: std::io::Error,
+        ressource_path: RessourcePath,
+        path: PathBuf,
+    },
+
     #[error(
         "Ressource type mismatch. Ressource at {ressource_path} hat type {ressource_type} but was loaded with {expected_type}"
     )]
@@ -63,6 +133,24 @@ pub enum RessourceError {
         expected_type: &'static str,
     },
 
+    #[error(
+        "Data for ressource at: {ressource_path} exceeds the limit of {limit} bytes. OSPath: {path}"
+    )]
+    DataTooLarge {
+        ressource_path: RessourcePath,
+        path: PathBuf,
+        limit: u64,
+    },
+
+    #[error(
+        "Metadata id {metadata_id} does not match ressource at: {ressource_path}. OSPath: {path}"
+    )]
+    IdMismatch {
+        metadata_id: RessourceId,
+        ressource_path: RessourcePath,
+        path: PathBuf,
+    },
+
     #[error(
         "Ressource {ressource_type} had an error parsing ressource data of ressource at {ressource_path}. OSPath: {path}. Error: {error}"
     )]
@@ -73,6 +161,39 @@ pub enum RessourceError {
         error: Box<dyn std::error::Error>,
     },
 
+    #[error(
+        "Ressource {ressource_type} had an error generating a preview of ressource at {ressource_path}. OSPath: {path}. Error: {error}"
+    )]
+    Preview {
+        ressource_type: &'static str,
+        ressource_path: RessourcePath,
+        path: PathBuf,
+        error: Box<dyn std::error::Error>,
+    },
+
+    #[error(
+        "IO Error syncing {file} to disk for ressource at: {ressource_path}. OSPath: {path}. Error: {error}"
+    )]
+    Sync {
+        error: std::io::Error,
+        ressource_path: RessourcePath,
+        path: PathBuf,
+        file: PathBuf,
+    },
+
+    #[error("IO Error writing journal at {path}. Error: {error}")]
+    JournalIO {
+        error: std::io::Error,
+        path: PathBuf,
+    },
+
+    #[error("Malformed journal entry at {path} line {line}. Error: {error}")]
+    JournalFormat {
+        error: serde_json::Error,
+        path: PathBuf,
+        line: usize,
+    },
+
     #[error("{0}")]
     WriteDataError(#[from] WriteDataError),
 
@@ -90,13 +211,177 @@ pub enum RessourceError {
         ressource_path: RessourcePath,
     },
 
+    #[error(
+        "Can't create ressource. Parent ressource is not a folder: RessourcePath: {ressource_path}. Parent: {parent} has type {parent_type}"
+    )]
+    ParentNotAFolder {
+        ressource_path: RessourcePath,
+        parent: Box<RessourcePath>,
+        parent_type: String,
+    },
+
+    #[error(
+        "Can't resolve {ressource_path}. Component {component} is not a folder but has type {ressource_type}"
+    )]
+    PathComponentNotAFolder {
+        ressource_path: RessourcePath,
+        component: Box<RessourcePath>,
+        ressource_type: String,
+    },
+
+    #[error(
+        "Can't create ressource. Ancestor {ancestor} does not exist: RessourcePath: {ressource_path}"
+    )]
+    MissingAncestor {
+        ressource_path: RessourcePath,
+        ancestor: RessourcePath,
+    },
+
+    #[error(
+        "Can't create ressource. Vault root is not a directory: RessourcePath: {ressource_path}. OSPath: {path}. Root: {root}"
+    )]
+    RootNotADirectory {
+        path: PathBuf,
+        ressource_path: RessourcePath,
+        root: PathBuf,
+    },
+
+    #[error(
+        "IO Error checking vault root for ressource at: {ressource_path}. OSPath: {path}. Root: {root}. Error: {error}"
+    )]
+    RootIO {
+        error: std::io::Error,
+        path: PathBuf,
+        ressource_path: RessourcePath,
+        root: PathBuf,
+    },
+
+    #[error("Invalid ressource id {id} for ressource at: {ressource_path}. OSPath: {path}")]
+    InvalidRessourceId {
+        id: RessourceId,
+        path: PathBuf,
+        ressource_path: RessourcePath,
+    },
+
+    #[error(
+        "Ressource id {id} ends in the reserved suffix {suffix}. Ressource at: {ressource_path}"
+    )]
+    ReservedIdSuffix {
+        id: RessourceId,
+        ressource_path: RessourcePath,
+        suffix: &'static str,
+    },
+
+    #[error(
+        "Unable to append to the compressed data of ressource at: {ressource_path}. OSPath: {path}"
+    )]
+    AppendToCompressed {
+        ressource_path: RessourcePath,
+        path: PathBuf,
+    },
+
+    #[error("Ressource already exists at: {ressource_path}. OSPath: {path}")]
+    RessourceExists {
+        path: PathBuf,
+        ressource_path: RessourcePath,
+    },
+
+    #[error(
+        "IO Error checking whether ressource exists at: {ressource_path}. OSPath: {path}. Error: {error}"
+    )]
+    ExistsIO {
+        error: std::io::Error,
+        ressource_path: RessourcePath,
+        path: PathBuf,
+    },
+
+    #[error("IO Error renaming {from} to {to} for ressource at: {ressource_path}. Error: {error}")]
+    RenameIO {
+        error: std::io::Error,
+        ressource_path: RessourcePath,
+        from: PathBuf,
+        to: PathBuf,
+    },
+
+    #[error("IO Error deleting {path} for ressource at: {ressource_path}. Error: {error}")]
+    DeleteIO {
+        error: std::io::Error,
+        ressource_path: RessourcePath,
+        path: PathBuf,
+    },
+
+    #[error("Can't update folder at: {ressource_path}. Folders only change through their children")]
+    FolderUpdate { ressource_path: RessourcePath },
+
+    #[error("Can't delete folder at: {ressource_path}. It still contains {children} ressources")]
+    FolderNotEmpty {
+        ressource_path: RessourcePath,
+        children: usize,
+    },
+
+    #[error(
+        "Can't delete ressource at: {ressource_path}. It is referenced by: {}",
+        .dependents.iter().map(RessourcePath::to_id_string).collect::<Vec<_>>().join(", ")
+    )]
+    HasDependents {
+        ressource_path: RessourcePath,
+        dependents: Vec<RessourcePath>,
+    },
+
+    #[cfg(feature = "watch")]
+    #[error("Unable to watch ressource at: {ressource_path}. OSPath: {path}. Error: {error}")]
+    Watch {
+        error: Box<notify::Error>,
+        ressource_path: RessourcePath,
+        path: PathBuf,
+    },
+
+    #[error(
+        "Metadata of ressource at: {ressource_path} has schema version {version}, but only versions up to {supported} are supported"
+    )]
+    UnsupportedSchemaVersion {
+        ressource_path: RessourcePath,
+        version: u32,
+        supported: u32,
+    },
+
+    #[error("Timed out after {timeout:?} loading ressource at: {ressource_path}. OSPath: {path}")]
+    Timeout {
+        ressource_path: RessourcePath,
+        path: PathBuf,
+        timeout: std::time::Duration,
+    },
+
+    #[error(
+        "Ressource type {ressource_type} is not allowed in this vault. Ressource at: {ressource_path}"
+    )]
+    DisallowedType {
+        ressource_path: RessourcePath,
+        ressource_type: String,
+    },
+
+    #[error("Can't modify ressource at: {ressource_path}. The vault is opened read-only")]
+    ReadOnly { ressource_path: RessourcePath },
+
+    #[error("Can't move ressource at: {ressource_path} into its own subtree at: {target}")]
+    MoveIntoSelf {
+        ressource_path: RessourcePath,
+        target: RessourcePath,
+    },
+
+    #[error("Alias at {ressource_path} exceeds the maximum alias depth of {depth}")]
+    AliasDepthExceeded {
+        ressource_path: RessourcePath,
+        depth: usize,
+    },
+
     #[error(
         "Can't create ressource with folded Id: RessourcePath: {ressource_path}. OSPath: {path}. Folded: {folded}"
     )]
     RessourceIdFolded {
         path: PathBuf,
         ressource_path: RessourcePath,
-        folded: RessourcePath,
+        folded: Box<RessourcePath>,
     },
 
     #[error(
@@ -109,4 +394,195 @@ pub enum RessourceError {
     },
 }
 
+impl RessourceError {
+    pub fn metadata_io(error: std::io::Error, path: &RessourcePath) -> Self {
+        RessourceError::MetadataIO {
+            error,
+            ressource_path: path.clone(),
+            path: path.resolve(),
+        }
+    }
+
+    pub fn write_metadata_io(error: std::io::Error, path: &RessourcePath) -> Self {
+        RessourceError::WriteMetadataIO {
+            error,
+            ressource_path: path.clone(),
+            path: path.resolve(),
+        }
+    }
+
+    pub fn metadata_serialize(error: serde_json::Error, path: &RessourcePath) -> Self {
+        RessourceError::MetadataSerialize {
+            error,
+            ressource_path: path.clone(),
+            path: path.resolve(),
+        }
+    }
+
+    pub fn metadata_format(error: serde_json::Error, path: &RessourcePath) -> Self {
+        RessourceError::MetadataFormat {
+            error,
+            ressource_path: path.clone(),
+            path: path.resolve(),
+        }
+    }
+
+    pub fn exists_io(error: std::io::Error, path: &RessourcePath) -> Self {
+        RessourceError::ExistsIO {
+            error,
+            ressource_path: path.clone(),
+            path: path.resolve(),
+        }
+    }
+
+    pub fn read_data_io(error: std::io::Error, path: &RessourcePath, os_path: &Path) -> Self {
+        RessourceError::ReadDataIO {
+            error,
+            ressource_path: path.clone(),
+            path: os_path.to_path_buf(),
+        }
+    }
+
+    pub fn write_data_io(error: std::io::Error, path: &RessourcePath, os_path: &Path) -> Self {
+        RessourceError::WriteDataIO {
+            error,
+            ressource_path: path.clone(),
+            path: os_path.to_path_buf(),
+        }
+    }
+
+    pub fn delete_io(error: std::io::Error, path: &RessourcePath, os_path: &Path) -> Self {
+        RessourceError::DeleteIO {
+            error,
+            ressource_path: path.clone(),
+            path: os_path.to_path_buf(),
+        }
+    }
+
+    pub fn rename_io(error: std::io::Error, path: &RessourcePath, from: &Path, to: &Path) -> Self {
+        RessourceError::RenameIO {
+            error,
+            ressource_path: path.clone(),
+            from: from.to_path_buf(),
+            to: to.to_path_buf(),
+        }
+    }
+
+    pub fn data_too_large(path: &RessourcePath, limit: u64) -> Self {
+        RessourceError::DataTooLarge {
+            ressource_path: path.clone(),
+            path: path.resolve(),
+            limit,
+        }
+    }
+
+    pub fn root_io(error: std::io::Error, path: &RessourcePath, root: &Path) -> Self {
+        RessourceError::RootIO {
+            error,
+            path: path.resolve(),
+            ressource_path: path.clone(),
+            root: root.to_path_buf(),
+        }
+    }
+
+    pub fn root_not_a_directory(path: &RessourcePath, root: &Path) -> Self {
+        RessourceError::RootNotADirectory {
+            path: path.resolve(),
+            ressource_path: path.clone(),
+            root: root.to_path_buf(),
+        }
+    }
+
+    pub fn invalid_ressource_id(id: &RessourceId, path: &RessourcePath) -> Self {
+        RessourceError::InvalidRessourceId {
+            id: id.clone(),
+            path: path.resolve(),
+            ressource_path: path.clone(),
+        }
+    }
+
+    pub fn parent_not_a_folder(
+        path: &RessourcePath,
+        parent: RessourcePath,
+        parent_type: String,
+    ) -> Self {
+        RessourceError::ParentNotAFolder {
+            ressource_path: path.clone(),
+            parent: Box::new(parent),
+            parent_type,
+        }
+    }
+
+    pub fn parent_ressource(path: &RessourcePath, folder_error: RessourceError) -> Self {
+        RessourceError::ParentRessource {
+            path: path.resolve(),
+            ressource_path: path.clone(),
+            folder_error: Box::new(folder_error),
+        }
+    }
+
+    pub fn missing_ancestor(path: &RessourcePath, ancestor: RessourcePath) -> Self {
+        RessourceError::MissingAncestor {
+            ressource_path: path.clone(),
+            ancestor,
+        }
+    }
+
+    pub fn path_component_not_a_folder(
+        path: &RessourcePath,
+        component: RessourcePath,
+        ressource_type: String,
+    ) -> Self {
+        RessourceError::PathComponentNotAFolder {
+            ressource_path: path.clone(),
+            component: Box::new(component),
+            ressource_type,
+        }
+    }
+
+    pub fn invalid_data(
+        ressource_type: &'static str,
+        path: &RessourcePath,
+        error: Box<dyn std::error::Error>,
+    ) -> Self {
+        RessourceError::InvalidData {
+            ressource_type,
+            ressource_path: path.clone(),
+            path: path.resolve(),
+            error,
+        }
+    }
+
+    pub fn sync(error: std::io::Error, path: &RessourcePath, file: &Path) -> Self {
+        RessourceError::Sync {
+            error,
+            ressource_path: path.clone(),
+            path: path.resolve(),
+            file: file.to_path_buf(),
+        }
+    }
+
+    pub fn timeout(path: &RessourcePath, timeout: std::time::Duration) -> Self {
+        RessourceError::Timeout {
+            ressource_path: path.clone(),
+            path: path.resolve(),
+            timeout,
+        }
+    }
+
+    pub fn ressource_at_root(path: &RessourcePath) -> Self {
+        RessourceError::RessourceAtRoot {
+            ressource_path: path.clone(),
+            path: path.resolve(),
+        }
+    }
+
+    pub fn ressource_exists(path: &RessourcePath) -> Self {
+        RessourceError::RessourceExists {
+            ressource_path: path.clone(),
+            path: path.resolve(),
+        }
+    }
+}
+
 pub type RessourceResult<T> = Result<T, RessourceError>;
@@ -1,10 +1,23 @@
 use crate::{
-    path::RessourceId,
+    backend::Backend,
+    error::RessourceResult,
+    index::IndexCache,
+    meta::RessourceMetadata,
+    path::{NamingScheme, RessourceId, RessourcePath},
+    ressource::Ressource,
+    ressources::Ressources,
     traits::{ReadableRessource, RessourceType, WritableRessource},
 };
-use std::path::{Path, PathBuf};
+use futures::{Stream, StreamExt, TryStreamExt, future, stream};
+use std::{
+    collections::HashSet,
+    ffi::OsString,
+    io,
+    path::{Path, PathBuf},
+    pin::pin,
+    sync::Arc,
+};
 use thiserror::Error;
-use tokio::fs;
 
 #[derive(Error, Debug)]
 #[allow(clippy::enum_variant_names)]
@@ -44,17 +57,196 @@ pub enum FolderRessourceError {
 #[derive(Debug, Clone)]
 pub struct FolderRessource {
     pub ressources: Vec<RessourceId>,
+    pub skipped: usize,
 }
 
 impl FolderRessource {
     pub fn new() -> Self {
         FolderRessource {
             ressources: Vec::new(),
+            skipped: 0,
         }
     }
 
     pub fn new_with_content(ressources: Vec<RessourceId>) -> Self {
-        FolderRessource { ressources }
+        FolderRessource {
+            ressources,
+            skipped: 0,
+        }
+    }
+
+    pub fn into_ressource_set(self) -> HashSet<RessourceId> {
+        self.ressources.into_iter().collect()
+    }
+
+    async fn children_metadata_many<B: Backend>(
+        ressources: &Ressources<B>,
+        parent: &RessourcePath,
+        concurrency: usize,
+    ) -> RessourceResult<Vec<RessourceMetadata>> {
+        let folder = Ressource::<FolderRessource>::load(ressources, parent.clone()).await?;
+        let paths = folder
+            .data
+            .ressources
+            .into_iter()
+            .map(|id| parent.with_child(id))
+            .collect();
+
+        ressources
+            .load_metadata_many(paths, concurrency)
+            .await
+            .into_iter()
+            .collect()
+    }
+
+    pub async fn children_is_folder<B: Backend>(
+        ressources: &Ressources<B>,
+        parent: &RessourcePath,
+        concurrency: usize,
+    ) -> RessourceResult<Vec<(RessourceId, bool)>> {
+        Ok(
+            Self::children_metadata_many(ressources, parent, concurrency)
+                .await?
+                .into_iter()
+                .map(|m| (m.id, m.type_id == FolderRessource::id()))
+                .collect(),
+        )
+    }
+
+    pub async fn children_with_extensions<B: Backend>(
+        ressources: &Ressources<B>,
+        parent: &RessourcePath,
+        concurrency: usize,
+    ) -> RessourceResult<Vec<(RessourceId, String)>> {
+        Ok(
+            Self::children_metadata_many(ressources, parent, concurrency)
+                .await?
+                .into_iter()
+                .map(|m| (m.id, m.data_extension))
+                .collect(),
+        )
+    }
+
+    pub async fn max_numeric_id<B: Backend>(
+        ressources: &Ressources<B>,
+        parent: &RessourcePath,
+    ) -> RessourceResult<Option<u64>> {
+        let folder = Ressource::<FolderRessource>::load(ressources, parent.clone()).await?;
+        Ok(folder
+            .data
+            .ressources
+            .iter()
+            .filter_map(|id| id.parse::<u64>().ok())
+            .max())
+    }
+
+    pub fn stream<'a, B: Backend>(
+        backend: &'a B,
+        path: &'a Path,
+        naming: NamingScheme,
+    ) -> impl Stream<Item = Result<RessourceId, FolderRessourceError>> + 'a {
+        stream::once(backend.read_dir(path))
+            .map(move |entries| match entries {
+                Ok(entries) => entries
+                    .filter_map(move |entry| future::ready(Self::entry_id(path, naming, entry)))
+                    .left_stream(),
+                Err(e) => stream::once(async move {
+                    Err(FolderRessourceError::CheckingForFolder {
+                        path: path.to_path_buf(),
+                        error: e,
+                    })
+                })
+                .right_stream(),
+            })
+            .flatten()
+    }
+
+    fn entry_id(
+        path: &Path,
+        naming: NamingScheme,
+        entry: std::io::Result<OsString>,
+    ) -> Option<Result<RessourceId, FolderRessourceError>> {
+        let filename = match entry {
+            Ok(filename) => filename,
+            Err(e) => {
+                return Some(Err(FolderRessourceError::NextEntry {
+                    path: path.to_path_buf(),
+                    error: e,
+                }));
+            }
+        };
+
+        let filename = match filename.into_string() {
+            Ok(filename) => filename,
+            Err(e) => {
+                return Some(Err(FolderRessourceError::Filename {
+                    path: path.to_path_buf(),
+                    filename: format!("{}", e.display()),
+                }));
+            }
+        };
+
+        naming
+            .id_from_metadata_file_name(&filename)
+            .map(|id| Ok(id.to_string()))
+    }
+
+    pub fn children_metadata<'a, B: Backend>(
+        ressources: &'a Ressources<B>,
+        parent: &'a RessourcePath,
+    ) -> impl Stream<Item = RessourceResult<RessourceMetadata>> + 'a {
+        stream::once(Ressource::<FolderRessource>::load(
+            ressources,
+            parent.clone(),
+        ))
+        .map(move |folder| match folder {
+            Ok(folder) => {
+                let cache = Arc::new(IndexCache::default());
+                stream::iter(folder.data.ressources)
+                    .then(move |id| {
+                        let cache = cache.clone();
+                        async move {
+                            RessourceMetadata::load_cached(
+                                ressources,
+                                &parent.with_child(id),
+                                &cache,
+                            )
+                            .await
+                        }
+                    })
+                    .left_stream()
+            }
+            Err(e) => stream::once(async { Err(e) }).right_stream(),
+        })
+        .flatten()
+    }
+
+    pub fn complete_children<'a, B: Backend>(
+        ressources: &'a Ressources<B>,
+        parent: &'a RessourcePath,
+    ) -> impl Stream<Item = RessourceResult<RessourcePath>> + 'a {
+        Self::children_metadata(ressources, parent).filter_map(move |metadata| {
+            future::ready(match metadata {
+                Ok(metadata) if metadata.is_incomplete() => None,
+                Ok(metadata) => Some(Ok(parent.with_child(metadata.id))),
+                Err(e) => Some(Err(e)),
+            })
+        })
+    }
+
+    pub fn subfolders<'a, B: Backend>(
+        ressources: &'a Ressources<B>,
+        parent: &'a RessourcePath,
+    ) -> impl Stream<Item = RessourceResult<RessourcePath>> + 'a {
+        Self::children_metadata(ressources, parent).filter_map(move |metadata| {
+            future::ready(match metadata {
+                Ok(metadata) if metadata.type_id == FolderRessource::id() => {
+                    Some(Ok(parent.with_child(metadata.id)))
+                }
+                Ok(_) => None,
+                Err(e) => Some(Err(e)),
+            })
+        })
     }
 }
 
@@ -68,51 +260,72 @@ impl RessourceType for FolderRessource {
     fn id() -> &'static str {
         "core/folder"
     }
+
+    fn display_name() -> &'static str {
+        "Folder"
+    }
 }
 
 impl ReadableRessource for FolderRessource {
     type Error = FolderRessourceError;
-    async fn read(path: &Path) -> Result<Self, FolderRessourceError> {
-        let mut stream =
-            fs::read_dir(path)
-                .await
-                .map_err(|e| FolderRessourceError::CheckingForFolder {
-                    path: path.to_path_buf(),
-                    error: e,
-                })?;
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            err,
+            fields(path = %path.display())
+        )
+    )]
+    async fn read<B: Backend>(backend: &B, path: &Path) -> Result<Self, FolderRessourceError> {
+        Ok(Self::new_with_content(
+            Self::stream(backend, path, NamingScheme::default())
+                .try_collect()
+                .await?,
+        ))
+    }
 
-        let mut ressources = Vec::new();
+    async fn read_in<B: Backend>(
+        ressources: &Ressources<B>,
+        path: &Path,
+    ) -> Result<Self, FolderRessourceError> {
+        if ressources.naming == NamingScheme::Index {
+            let index = match ressources.backend.is_dir(path).await {
+                Ok(true) => ressources.read_index(path).await,
+                Ok(false) => Err(io::Error::from(io::ErrorKind::NotFound)),
+                Err(e) => Err(e),
+            }
+            .map_err(|e| FolderRessourceError::CheckingForFolder {
+                path: path.to_path_buf(),
+                error: e,
+            })?;
+            return Ok(Self::new_with_content(index.into_keys().collect()));
+        }
 
-        while let Some(entry) =
-            stream
-                .next_entry()
-                .await
-                .map_err(|e| FolderRessourceError::NextEntry {
-                    path: path.to_path_buf(),
-                    error: e,
-                })?
-        {
-            let filename =
-                entry
-                    .file_name()
-                    .into_string()
-                    .map_err(|e| FolderRessourceError::Filename {
-                        path: path.to_path_buf(),
-                        filename: format!("{}", e.display()),
-                    })?;
+        let mut folder = Self::new();
+        let mut stream = pin!(Self::stream(&ressources.backend, path, ressources.naming));
 
-            if let Some(ressource_id) = filename.strip_suffix(".meta.json") {
-                ressources.push(ressource_id.to_string());
+        while let Some(entry) = stream.next().await {
+            match entry {
+                Ok(id) => folder.ressources.push(id),
+                Err(FolderRessourceError::Filename { .. }) if ressources.lenient_filenames => {
+                    folder.skipped += 1;
+                }
+                Err(e) => return Err(e),
             }
         }
 
-        Ok(Self { ressources })
+        Ok(folder)
     }
 }
 
 impl WritableRessource for FolderRessource {
     type Error = FolderRessourceError;
-    async fn write(&self, path: &Path) -> Result<(), FolderRessourceError> {
+    async fn write<B: Backend>(
+        &self,
+        backend: &B,
+        path: &Path,
+    ) -> Result<(), FolderRessourceError> {
         if !self.ressources.is_empty() {
             return Err(FolderRessourceError::CreateNonEmptyFolder {
                 path: path.to_path_buf(),
@@ -120,7 +333,8 @@ impl WritableRessource for FolderRessource {
             });
         }
 
-        fs::create_dir(path)
+        backend
+            .create_dir(path)
             .await
             .map_err(|e| FolderRessourceError::CreatingFolder {
                 path: path.to_path_buf(),
@@ -132,3 +346,72 @@ impl WritableRessource for FolderRessource {
         ""
     }
 }
+
+#[cfg(all(test, feature = "test-util"))]
+mod tests {
+    use super::*;
+    use crate::{memory_backend::MemoryBackend, text_ressource::TextRessource};
+    use futures::executor::block_on;
+
+    async fn vault(naming: NamingScheme) -> Ressources<MemoryBackend> {
+        let mut ressources = Ressources::init_with_backend("/vault".into(), MemoryBackend::new())
+            .await
+            .unwrap();
+        ressources.naming = naming;
+
+        let folder = ressources.path(vec!["f".into()]);
+        let nested = folder.with_child("g");
+        Ressource::new(&ressources, folder, FolderRessource::new())
+            .await
+            .unwrap();
+        Ressource::new(&ressources, nested.clone(), FolderRessource::new())
+            .await
+            .unwrap();
+        for path in [nested.with_child("a"), ressources.path(vec!["b".into()])] {
+            Ressource::new(&ressources, path, TextRessource::new("text".into()))
+                .await
+                .unwrap();
+        }
+        ressources
+    }
+
+    async fn ids(ressources: &Ressources<MemoryBackend>, path: &Path) -> Vec<RessourceId> {
+        let mut ids = FolderRessource::read_in(ressources, path)
+            .await
+            .unwrap()
+            .ressources;
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn read_strips_naming_suffixes() {
+        block_on(async {
+            for naming in [
+                NamingScheme::Suffix,
+                NamingScheme::Hidden,
+                NamingScheme::Index,
+            ] {
+                let ressources = vault(naming).await;
+                let nested = ressources.path(vec!["f".into(), "g".into()]);
+                let nested_dir = naming.data_path(&nested, "");
+
+                assert_eq!(ids(&ressources, &ressources.root).await, ["b", "f"]);
+                assert_eq!(ids(&ressources, &nested_dir).await, ["a"]);
+            }
+        });
+    }
+
+    #[test]
+    fn read_uses_default_naming() {
+        block_on(async {
+            let ressources = vault(NamingScheme::default()).await;
+            let mut ids = FolderRessource::read(&ressources.backend, &ressources.root)
+                .await
+                .unwrap()
+                .ressources;
+            ids.sort();
+            assert_eq!(ids, ["b", "f"]);
+        });
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{
+    backend::Backend,
+    error::{RessourceError, RessourceResult},
+    folder_ressource::FolderRessource,
+    index::IndexCache,
+    meta::RessourceMetadata,
+    path::RessourcePath,
+    ressource::Ressource,
+    ressources::Ressources,
+    traits::RessourceType,
+};
+use std::io;
+
+#[derive(Debug, Default)]
+pub struct HealthReport {
+    pub problems: Vec<RessourceError>,
+    pub checked: usize,
+}
+
+impl HealthReport {
+    pub fn is_healthy(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+impl<B: Backend> Ressources<B> {
+    pub async fn health_check(&self, sample: usize) -> RessourceResult<HealthReport> {
+        let mut report = HealthReport::default();
+        let root_path = self.path(Vec::new());
+
+        match self.backend.is_dir(&self.root).await {
+            Ok(true) => {}
+            Ok(false) => report
+                .problems
+                .push(RessourceError::root_not_a_directory(&root_path, &self.root)),
+            Err(e) => report
+                .problems
+                .push(RessourceError::root_io(e, &root_path, &self.root)),
+        }
+
+        let root = match Ressource::<FolderRessource>::load(self, root_path.clone()).await {
+            Ok(root) => root,
+            Err(e) => {
+                report.problems.push(e);
+                return Ok(report);
+            }
+        };
+
+        let cache = IndexCache::default();
+        for id in root.data.ressources.into_iter().take(sample) {
+            report.checked += 1;
+            if let Err(e) =
+                RessourceMetadata::load_cached(self, &root_path.with_child(id), &cache).await
+            {
+                report.problems.push(e);
+            }
+        }
+
+        Ok(report)
+    }
+
+    pub async fn list_corrupt(
+        &self,
+        path: RessourcePath,
+    ) -> RessourceResult<Vec<(RessourcePath, RessourceError)>> {
+        let mut corrupt = Vec::new();
+        let mut folders = vec![path];
+        let cache = IndexCache::default();
+
+        while let Some(folder) = folders.pop() {
+            let folder_ressource =
+                match Ressource::<FolderRessource>::load(self, folder.clone()).await {
+                    Ok(folder_ressource) => folder_ressource,
+                    Err(e) => {
+                        corrupt.push((folder, e));
+                        continue;
+                    }
+                };
+
+            for id in folder_ressource.data.ressources {
+                let child = folder.with_child(id);
+                let metadata = match RessourceMetadata::load_cached(self, &child, &cache).await {
+                    Ok(metadata) => metadata,
+                    Err(e) => {
+                        corrupt.push((child, e));
+                        continue;
+                    }
+                };
+
+                if metadata.type_id == FolderRessource::id() {
+                    folders.push(child);
+                    continue;
+                }
+
+                let data_path = metadata.find_data_path(self, &child).await;
+                match self.backend.exists(&data_path).await {
+                    Ok(true) => {}
+                    Ok(false) => corrupt.push((
+                        child.clone(),
+                        RessourceError::read_data_io(
+                            io::Error::from(io::ErrorKind::NotFound),
+                            &child,
+                            &data_path,
+                        ),
+                    )),
+                    Err(e) => corrupt.push((
+                        child.clone(),
+                        RessourceError::read_data_io(e, &child, &data_path),
+                    )),
+                }
+            }
+        }
+
+        Ok(corrupt)
+    }
+}
@@ -0,0 +1,314 @@
+use crate::{
+    backend::{Backend, RetryBackend},
+    path::{NamingScheme, RessourceId, RessourcePath},
+    ressources::Ressources,
+};
+use futures::lock::Mutex;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+pub type MetadataIndex = BTreeMap<RessourceId, serde_json::Value>;
+
+/// Index files already read during one bulk operation, so each folder's `_index.json` is
+/// parsed once instead of once per child.
+#[derive(Debug, Default)]
+pub(crate) struct IndexCache(Mutex<HashMap<PathBuf, Arc<MetadataIndex>>>);
+
+fn indexed(path: &RessourcePath, naming: NamingScheme) -> Option<(PathBuf, &RessourceId)> {
+    match (naming, path.path.last()) {
+        (NamingScheme::Index, Some(id)) => Some((naming.metadata_path(path), id)),
+        _ => None,
+    }
+}
+
+async fn replace_file<B: Backend>(backend: &B, path: &Path, data: &[u8]) -> io::Result<()> {
+    let temp_path = path.with_added_extension("tmp");
+    let written = match backend.write(&temp_path, data).await {
+        Ok(()) => backend.rename(&temp_path, path).await,
+        Err(e) => Err(e),
+    };
+    if written.is_err() {
+        let _ = backend.remove_file(&temp_path).await;
+    }
+    written
+}
+
+fn invalid_data(error: serde_json::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+impl<B: Backend> Ressources<B> {
+    pub fn metadata_path(&self, path: &RessourcePath) -> PathBuf {
+        self.naming.metadata_path(path)
+    }
+
+    pub async fn read_index(&self, folder: &Path) -> io::Result<MetadataIndex> {
+        Self::read_index_file(&self.backend, &folder.join(NamingScheme::INDEX_FILE)).await
+    }
+
+    async fn read_index_file(backend: &B, index_path: &Path) -> io::Result<MetadataIndex> {
+        match backend.read(index_path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(invalid_data),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(MetadataIndex::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn update_index(
+        &self,
+        index_path: &Path,
+        update: impl FnOnce(&mut MetadataIndex) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let _guard = self.index_lock.lock().await;
+        let mut index = Self::read_index_file(&self.backend, index_path).await?;
+        update(&mut index)?;
+
+        let backend = RetryBackend::new(&self.backend, self.retry);
+        if index.is_empty() {
+            return match backend.remove_file(index_path).await {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            };
+        }
+
+        replace_file(
+            &backend,
+            index_path,
+            &self.metadata_json(&index).map_err(invalid_data)?,
+        )
+        .await
+    }
+
+    pub(crate) fn metadata_json<T: Serialize>(&self, value: &T) -> serde_json::Result<Vec<u8>> {
+        if self.pretty_metadata {
+            serde_json::to_vec_pretty(value)
+        } else {
+            serde_json::to_vec(value)
+        }
+    }
+
+    pub(crate) async fn read_metadata_file(&self, path: &RessourcePath) -> io::Result<Vec<u8>> {
+        self.read_metadata_file_cached(path, &IndexCache::default())
+            .await
+    }
+
+    pub(crate) async fn read_metadata_file_cached(
+        &self,
+        path: &RessourcePath,
+        cache: &IndexCache,
+    ) -> io::Result<Vec<u8>> {
+        if path.path.is_empty() {
+            return match self.backend.read(&self.metadata_path(path)).await {
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    let legacy_path = NamingScheme::legacy_root_metadata_path(&path.root);
+                    self.backend.read(&legacy_path).await.map_err(|_| e)
+                }
+                result => result,
+            };
+        }
+
+        let Some((index_path, id)) = indexed(path, self.naming) else {
+            return self.backend.read(&self.metadata_path(path)).await;
+        };
+
+        let index = {
+            let mut indexes = cache.0.lock().await;
+            match indexes.get(&index_path) {
+                Some(index) => index.clone(),
+                None => {
+                    let index = Arc::new(Self::read_index_file(&self.backend, &index_path).await?);
+                    indexes.insert(index_path.clone(), index.clone());
+                    index
+                }
+            }
+        };
+
+        match index.get(id) {
+            Some(metadata) => serde_json::to_vec(metadata).map_err(invalid_data),
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No entry for {id} in {}", index_path.display()),
+            )),
+        }
+    }
+
+    pub(crate) async fn write_metadata_file(
+        &self,
+        path: &RessourcePath,
+        metadata: &[u8],
+    ) -> io::Result<()> {
+        let Some((index_path, id)) = indexed(path, self.naming) else {
+            return replace_file(
+                &RetryBackend::new(&self.backend, self.retry),
+                &self.metadata_path(path),
+                metadata,
+            )
+            .await;
+        };
+
+        let metadata = serde_json::from_slice(metadata).map_err(invalid_data)?;
+        self.update_index(&index_path, |index| {
+            index.insert(id.clone(), metadata);
+            Ok(())
+        })
+        .await
+    }
+
+    pub(crate) async fn create_metadata_file(
+        &self,
+        path: &RessourcePath,
+        metadata: &[u8],
+    ) -> io::Result<()> {
+        let Some((index_path, id)) = indexed(path, self.naming) else {
+            return self
+                .backend
+                .create_new(&self.metadata_path(path), metadata)
+                .await;
+        };
+
+        let metadata = serde_json::from_slice(metadata).map_err(invalid_data)?;
+        self.update_index(&index_path, |index| {
+            if index.contains_key(id) {
+                return Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    format!("Entry for {id} already exists in {}", index_path.display()),
+                ));
+            }
+            index.insert(id.clone(), metadata);
+            Ok(())
+        })
+        .await
+    }
+
+    pub(crate) async fn remove_metadata_file(&self, path: &RessourcePath) -> io::Result<()> {
+        let Some((index_path, id)) = indexed(path, self.naming) else {
+            return self.backend.remove_file(&self.metadata_path(path)).await;
+        };
+
+        self.update_index(&index_path, |index| match index.remove(id) {
+            Some(_) => Ok(()),
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No entry for {id} in {}", index_path.display()),
+            )),
+        })
+        .await
+    }
+
+    pub(crate) async fn metadata_file_exists(&self, path: &RessourcePath) -> io::Result<bool> {
+        if path.path.is_empty() && !self.backend.exists(&self.metadata_path(path)).await? {
+            return self
+                .backend
+                .exists(&NamingScheme::legacy_root_metadata_path(&path.root))
+                .await;
+        }
+
+        let Some((index_path, id)) = indexed(path, self.naming) else {
+            return self.backend.exists(&self.metadata_path(path)).await;
+        };
+
+        Ok(Self::read_index_file(&self.backend, &index_path)
+            .await?
+            .contains_key(id))
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod tests {
+    use crate::{
+        backend::Backend, folder_ressource::FolderRessource, memory_backend::MemoryBackend,
+        path::NamingScheme, ressource::Ressource, ressources::Ressources,
+        text_ressource::TextRessource,
+    };
+    use futures::{StreamExt, executor::block_on};
+    use std::{ffi::OsString, path::Path};
+
+    async fn vault() -> Ressources<MemoryBackend> {
+        let mut ressources = Ressources::init_with_backend("/vault".into(), MemoryBackend::new())
+            .await
+            .unwrap();
+        ressources.naming = NamingScheme::Index;
+        ressources
+    }
+
+    async fn files(ressources: &Ressources<MemoryBackend>, dir: &str) -> Vec<OsString> {
+        ressources
+            .backend
+            .read_dir(Path::new(dir))
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await
+    }
+
+    #[test]
+    fn stores_child_metadata_in_one_index_per_folder() {
+        block_on(async {
+            let ressources = vault().await;
+            let folder = ressources.path(vec!["f".into()]);
+            Ressource::new(&ressources, folder.clone(), FolderRessource::new())
+                .await
+                .unwrap();
+            for id in ["a", "b"] {
+                Ressource::new(
+                    &ressources,
+                    folder.with_child(id),
+                    TextRessource::new(id.into()),
+                )
+                .await
+                .unwrap();
+            }
+
+            assert_eq!(
+                files(&ressources, "/vault").await,
+                [".meta.json", "_index.json", "f.data"]
+            );
+            assert_eq!(
+                files(&ressources, "/vault/f.data").await,
+                ["_index.json", "a.data.txt", "b.data.txt"]
+            );
+
+            let index = ressources
+                .read_index(Path::new("/vault/f.data"))
+                .await
+                .unwrap();
+            assert_eq!(index.keys().collect::<Vec<_>>(), ["a", "b"]);
+
+            let b = Ressource::<TextRessource>::load(&ressources, folder.with_child("b"))
+                .await
+                .unwrap();
+            assert_eq!(b.data, TextRessource::new("b".into()));
+
+            let mut walked: Vec<_> = ressources
+                .walk(ressources.path(Vec::new()))
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|(path, _)| path.to_id_string())
+                .collect();
+            walked.sort();
+            assert_eq!(walked, ["f", "f/a", "f/b"]);
+        });
+    }
+
+    #[test]
+    fn removes_index_with_last_child() {
+        block_on(async {
+            let ressources = vault().await;
+            let path = ressources.path(vec!["a".into()]);
+            Ressource::new(&ressources, path.clone(), TextRessource::new("a".into()))
+                .await
+                .unwrap();
+
+            ressources.delete(path.clone()).await.unwrap();
+            assert!(!ressources.exists(&path).await.unwrap());
+            assert_eq!(files(&ressources, "/vault").await, [".meta.json"]);
+        });
+    }
+}
@@ -0,0 +1,42 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Mutex, OnceLock, RwLock},
+};
+
+pub(crate) fn intern_str(value: String) -> &'static str {
+    static INTERNED: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    let mut interned = INTERNED
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+
+    if let Some(value) = interned.get(value.as_str()) {
+        return value;
+    }
+
+    let value: &'static str = Box::leak(value.into_boxed_str());
+    interned.insert(value);
+    value
+}
+
+/// Like `intern_str`, but only formats `{base}{separator}{suffix}` the first time it is asked for.
+pub(crate) fn intern_joined(
+    base: &'static str,
+    separator: char,
+    suffix: &'static str,
+) -> &'static str {
+    type Joined = HashMap<(&'static str, char, &'static str), &'static str>;
+    static JOINED: OnceLock<RwLock<Joined>> = OnceLock::new();
+    let joined = JOINED.get_or_init(Default::default);
+    let key = (base, separator, suffix);
+
+    if let Some(value) = joined.read().unwrap_or_else(|e| e.into_inner()).get(&key) {
+        return value;
+    }
+
+    joined
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(key)
+        .or_insert_with(|| intern_str(format!("{base}{separator}{suffix}")))
+}
@@ -0,0 +1,194 @@
+use crate::{
+    backend::Backend,
+    error::{RessourceError, RessourceResult},
+    path::RessourcePath,
+    ressources::Ressources,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+pub enum JournalOperation {
+    Create {
+        path: RessourcePath,
+    },
+    Update {
+        path: RessourcePath,
+    },
+    Delete {
+        path: RessourcePath,
+    },
+    Move {
+        from: RessourcePath,
+        to: RessourcePath,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub time: DateTime<Utc>,
+    #[serde(flatten)]
+    pub operation: JournalOperation,
+}
+
+impl<B: Backend> Ressources<B> {
+    pub fn with_journal(mut self, path: PathBuf) -> Self {
+        self.journal = Some(path);
+        self
+    }
+
+    pub(crate) async fn journal(&self, operation: JournalOperation) -> RessourceResult<()> {
+        let Some(journal) = &self.journal else {
+            return Ok(());
+        };
+
+        let entry = JournalEntry {
+            time: Utc::now(),
+            operation,
+        };
+        let mut line =
+            serde_json::to_string(&entry).map_err(|e| RessourceError::JournalFormat {
+                error: e,
+                path: journal.clone(),
+                line: 0,
+            })?;
+        line.push('\n');
+
+        self.backend
+            .append(journal, line.as_bytes())
+            .await
+            .map_err(|e| RessourceError::JournalIO {
+                error: e,
+                path: journal.clone(),
+            })
+    }
+
+    pub async fn replay(&self) -> RessourceResult<Vec<JournalEntry>> {
+        let Some(journal) = &self.journal else {
+            return Ok(Vec::new());
+        };
+
+        let data = match self.backend.read(journal).await {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(RessourceError::JournalIO {
+                    error: e,
+                    path: journal.clone(),
+                });
+            }
+        };
+
+        data.split(|byte| *byte == b'\n')
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(i, line)| {
+                let mut entry: JournalEntry =
+                    serde_json::from_slice(line).map_err(|e| RessourceError::JournalFormat {
+                        error: e,
+                        path: journal.clone(),
+                        line: i + 1,
+                    })?;
+                entry.operation.set_root(&self.root);
+                Ok(entry)
+            })
+            .collect()
+    }
+}
+
+impl JournalOperation {
+    fn set_root(&mut self, root: &std::path::Path) {
+        match self {
+            JournalOperation::Create { path }
+            | JournalOperation::Update { path }
+            | JournalOperation::Delete { path } => {
+                path.root = root.to_path_buf();
+            }
+            JournalOperation::Move { from, to } => {
+                from.root = root.to_path_buf();
+                to.root = root.to_path_buf();
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod tests {
+    use super::JournalOperation;
+    use crate::{
+        backend::Backend, error::RessourceError, memory_backend::MemoryBackend,
+        ressource::Ressource, ressources::Ressources, text_ressource::TextRessource,
+    };
+    use futures::executor::block_on;
+    use std::path::Path;
+
+    #[test]
+    fn replay_returns_operations_in_order() {
+        block_on(async {
+            let ressources = Ressources::init_with_backend("/vault".into(), MemoryBackend::new())
+                .await
+                .unwrap()
+                .with_journal("/journal.log".into());
+            assert!(ressources.replay().await.unwrap().is_empty());
+
+            let a = ressources.path(vec!["a".into()]);
+            let b = ressources.path(vec!["b".into()]);
+            Ressource::new(&ressources, a.clone(), TextRessource::new("a".into()))
+                .await
+                .unwrap();
+            Ressource::update(&ressources, a.clone(), TextRessource::new("b".into()))
+                .await
+                .unwrap();
+            Ressource::<TextRessource>::rename(&ressources, a.clone(), "b".into())
+                .await
+                .unwrap();
+            ressources.delete(b.clone()).await.unwrap();
+
+            let operations: Vec<_> = ressources
+                .replay()
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|entry| entry.operation)
+                .collect();
+            assert!(matches!(
+                operations.as_slice(),
+                [
+                    JournalOperation::Create { path: created },
+                    JournalOperation::Update { path: updated },
+                    JournalOperation::Move { from, to },
+                    JournalOperation::Delete { path: deleted },
+                ] if *created == a && *updated == a && *from == a && *to == b && *deleted == b
+            ));
+        });
+    }
+
+    #[test]
+    fn replay_reports_malformed_line() {
+        block_on(async {
+            let ressources = Ressources::init_with_backend("/vault".into(), MemoryBackend::new())
+                .await
+                .unwrap()
+                .with_journal("/journal.log".into());
+            Ressource::new(
+                &ressources,
+                ressources.path(vec!["a".into()]),
+                TextRessource::new("a".into()),
+            )
+            .await
+            .unwrap();
+            ressources
+                .backend
+                .append(Path::new("/journal.log"), b"not json\n")
+                .await
+                .unwrap();
+
+            assert!(matches!(
+                ressources.replay().await,
+                Err(RessourceError::JournalFormat { line: 2, .. })
+            ));
+        });
+    }
+}
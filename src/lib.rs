@@ -1,6 +1,36 @@
+pub mod alias_ressource;
+#[cfg(feature = "tar")]
+pub mod archive;
+pub mod backend;
+#[cfg(feature = "compression")]
+pub mod compressed;
+pub mod dedupe;
+pub mod diff;
+#[cfg(feature = "encryption")]
+pub mod encrypted;
 pub mod error;
 pub mod folder_ressource;
+pub mod health;
+pub mod index;
+#[cfg(any(feature = "compression", feature = "encryption"))]
+mod intern;
+pub mod journal;
+#[cfg(feature = "test-util")]
+pub mod memory_backend;
 pub mod meta;
+#[cfg(feature = "mmap")]
+pub mod mmap_ressource;
 pub mod path;
+pub mod relocation;
+pub mod reservation;
 pub mod ressource;
+pub mod ressources;
+pub mod simple_ressource;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+pub mod text_ressource;
 pub mod traits;
+pub mod transaction;
+pub mod vacuum;
+#[cfg(feature = "watch")]
+pub mod watch;
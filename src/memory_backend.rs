@@ -0,0 +1,279 @@
+use crate::backend::Backend;
+use futures::stream;
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::OsString,
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+type File = Arc<Mutex<Vec<u8>>>;
+
+fn contents(file: &File) -> MutexGuard<'_, Vec<u8>> {
+    file.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[derive(Debug, Default)]
+struct MemoryState {
+    files: HashMap<PathBuf, File>,
+    dirs: HashSet<PathBuf>,
+}
+
+impl MemoryState {
+    fn is_dir(&self, path: &Path) -> bool {
+        path.parent().is_none() || path.as_os_str().is_empty() || self.dirs.contains(path)
+    }
+
+    fn check_parent(&self, path: &Path) -> io::Result<()> {
+        match path.parent() {
+            Some(parent) if !self.is_dir(parent) => Err(not_found(parent)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct MemoryBackend {
+    state: Mutex<MemoryState>,
+}
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> MutexGuard<'_, MemoryState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!(
+            "MemoryBackend: No such file or directory: {}",
+            path.display()
+        ),
+    )
+}
+
+fn already_exists(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        format!("MemoryBackend: File exists: {}", path.display()),
+    )
+}
+
+fn is_a_directory(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::IsADirectory,
+        format!("MemoryBackend: Is a directory: {}", path.display()),
+    )
+}
+
+fn rebase(path: &Path, from: &Path, to: &Path) -> Option<PathBuf> {
+    path.strip_prefix(from).ok().map(|rest| to.join(rest))
+}
+
+impl Backend for MemoryBackend {
+    type ReadDir = stream::Iter<std::vec::IntoIter<io::Result<OsString>>>;
+
+    async fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let state = self.state();
+        if let Some(file) = state.files.get(path) {
+            return Ok(contents(file).clone());
+        }
+        if state.is_dir(path) {
+            return Err(is_a_directory(path));
+        }
+        Err(not_found(path))
+    }
+
+    async fn write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        let mut state = self.state();
+        state.check_parent(path)?;
+        if state.is_dir(path) {
+            return Err(is_a_directory(path));
+        }
+        match state.files.get(path) {
+            Some(file) => *contents(file) = data.to_vec(),
+            None => {
+                state
+                    .files
+                    .insert(path.to_path_buf(), Arc::new(Mutex::new(data.to_vec())));
+            }
+        }
+        Ok(())
+    }
+
+    async fn create_new(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        let mut state = self.state();
+        state.check_parent(path)?;
+        if state.is_dir(path) || state.files.contains_key(path) {
+            return Err(already_exists(path));
+        }
+        state
+            .files
+            .insert(path.to_path_buf(), Arc::new(Mutex::new(data.to_vec())));
+        Ok(())
+    }
+
+    async fn remove_file(&self, path: &Path) -> io::Result<()> {
+        let mut state = self.state();
+        if state.files.remove(path).is_some() {
+            return Ok(());
+        }
+        if state.is_dir(path) {
+            return Err(is_a_directory(path));
+        }
+        Err(not_found(path))
+    }
+
+    async fn read_dir(&self, path: &Path) -> io::Result<Self::ReadDir> {
+        let state = self.state();
+        if !state.is_dir(path) {
+            return Err(not_found(path));
+        }
+
+        let mut entries: Vec<OsString> = state
+            .files
+            .keys()
+            .chain(state.dirs.iter())
+            .filter(|entry| entry.parent() == Some(path))
+            .filter_map(|entry| entry.file_name().map(|name| name.to_os_string()))
+            .collect();
+        entries.sort();
+
+        Ok(stream::iter(
+            entries.into_iter().map(Ok).collect::<Vec<_>>(),
+        ))
+    }
+
+    async fn create_dir(&self, path: &Path) -> io::Result<()> {
+        let mut state = self.state();
+        state.check_parent(path)?;
+        if state.is_dir(path) || state.files.contains_key(path) {
+            return Err(already_exists(path));
+        }
+        state.dirs.insert(path.to_path_buf());
+        Ok(())
+    }
+
+    async fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        let mut state = self.state();
+        if !state.dirs.contains(path) {
+            return Err(not_found(path));
+        }
+
+        if state
+            .files
+            .keys()
+            .chain(state.dirs.iter())
+            .any(|entry| entry.parent() == Some(path))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::DirectoryNotEmpty,
+                format!("MemoryBackend: Directory not empty: {}", path.display()),
+            ));
+        }
+
+        state.dirs.remove(path);
+        Ok(())
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let mut state = self.state();
+        state.check_parent(to)?;
+
+        if let Some(data) = state.files.remove(from) {
+            state.files.insert(to.to_path_buf(), data);
+            return Ok(());
+        }
+
+        if !state.dirs.contains(from) {
+            return Err(not_found(from));
+        }
+
+        let (moved, kept): (HashSet<_>, HashSet<_>) =
+            state.dirs.drain().partition(|dir| dir.starts_with(from));
+        state.dirs = kept;
+        state
+            .dirs
+            .extend(moved.iter().filter_map(|dir| rebase(dir, from, to)));
+
+        let (moved, kept): (HashMap<_, _>, HashMap<_, _>) = state
+            .files
+            .drain()
+            .partition(|(file, _)| file.starts_with(from));
+        state.files = kept;
+        state.files.extend(
+            moved
+                .into_iter()
+                .filter_map(|(file, data)| rebase(&file, from, to).map(|file| (file, data))),
+        );
+
+        Ok(())
+    }
+
+    async fn hard_link(&self, original: &Path, link: &Path) -> io::Result<()> {
+        let mut state = self.state();
+        state.check_parent(link)?;
+        if state.is_dir(link) || state.files.contains_key(link) {
+            return Err(already_exists(link));
+        }
+        let Some(file) = state.files.get(original).cloned() else {
+            return Err(not_found(original));
+        };
+        state.files.insert(link.to_path_buf(), file);
+        Ok(())
+    }
+
+    async fn is_dir(&self, path: &Path) -> io::Result<bool> {
+        Ok(self.state().is_dir(path))
+    }
+
+    async fn len(&self, path: &Path) -> io::Result<u64> {
+        let state = self.state();
+        if let Some(file) = state.files.get(path) {
+            return Ok(contents(file).len() as u64);
+        }
+        if state.is_dir(path) {
+            return Err(is_a_directory(path));
+        }
+        Err(not_found(path))
+    }
+
+    async fn link_count(&self, path: &Path) -> io::Result<u64> {
+        let state = self.state();
+        if let Some(file) = state.files.get(path) {
+            return Ok(Arc::strong_count(file) as u64);
+        }
+        if state.is_dir(path) {
+            return Err(is_a_directory(path));
+        }
+        Err(not_found(path))
+    }
+
+    async fn same_file(&self, a: &Path, b: &Path) -> io::Result<bool> {
+        let state = self.state();
+        match (state.files.get(a), state.files.get(b)) {
+            (Some(a), Some(b)) => Ok(Arc::ptr_eq(a, b)),
+            (None, _) => Err(not_found(a)),
+            (_, None) => Err(not_found(b)),
+        }
+    }
+
+    async fn exists(&self, path: &Path) -> io::Result<bool> {
+        let state = self.state();
+        Ok(state.files.contains_key(path) || state.is_dir(path))
+    }
+
+    async fn sync(&self, path: &Path) -> io::Result<()> {
+        let state = self.state();
+        if state.files.contains_key(path) || state.is_dir(path) {
+            return Ok(());
+        }
+        Err(not_found(path))
+    }
+}
@@ -1,24 +1,221 @@
-use std::{marker::PhantomData, path::PathBuf};
+use std::{
+    io,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
 
 use chrono::{DateTime, Utc};
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
-use tokio::fs::read_to_string;
 
 use crate::{
+    backend::Backend,
     error::{RessourceError, RessourceResult},
-    path::{RessourceId, RessourcePath},
+    folder_ressource::FolderRessource,
+    index::IndexCache,
+    journal::JournalOperation,
+    path::{NamingScheme, RessourceId, RessourcePath},
+    reservation::INCOMPLETE_KEY,
+    ressources::Ressources,
     traits::{RessourceType, WritableRessource},
 };
 
+pub const METADATA_SCHEMA_VERSION: u32 = 1;
+
+fn legacy_schema_version() -> u32 {
+    1
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RessourceMetadata {
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u32,
     pub data_extension: String,
     pub type_id: String,
     pub time: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<DateTime<Utc>>,
     pub id: RessourceId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime: Option<String>,
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug)]
+impl RessourceMetadata {
+    pub fn last_modified(&self) -> DateTime<Utc> {
+        self.modified.unwrap_or(self.time)
+    }
+
+    pub fn from_slice(bytes: &[u8]) -> RessourceResult<Self> {
+        serde_json::from_slice(bytes).map_err(|e| RessourceError::MetadataBytes { error: e })
+    }
+
+    pub fn to_vec(&self) -> RessourceResult<Vec<u8>> {
+        serde_json::to_vec(self).map_err(|e| RessourceError::MetadataBytes { error: e })
+    }
+
+    fn upgrade(&mut self) -> bool {
+        if self.schema_version >= METADATA_SCHEMA_VERSION {
+            return false;
+        }
+
+        self.schema_version = METADATA_SCHEMA_VERSION;
+        true
+    }
+
+    pub fn is_incomplete(&self) -> bool {
+        self.extra
+            .get(INCOMPLETE_KEY)
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false)
+    }
+
+    pub fn clone_extras_from(&mut self, other: &RessourceMetadata) {
+        self.extra.extend(
+            other
+                .extra
+                .iter()
+                .filter(|(key, _)| *key != INCOMPLETE_KEY)
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+    }
+
+    pub fn references(&self) -> Vec<Vec<RessourceId>> {
+        self.extra
+            .get("references")
+            .and_then(serde_json::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(serde_json::Value::as_str)
+            .map(|reference| reference.split('/').map(str::to_string).collect())
+            .collect()
+    }
+
+    pub fn data_path(&self, path: &RessourcePath) -> PathBuf {
+        // Data file names are the same under every naming scheme.
+        NamingScheme::default().data_path(path, &self.data_extension)
+    }
+
+    pub async fn find_data_path<B: Backend>(
+        &self,
+        ressources: &Ressources<B>,
+        path: &RessourcePath,
+    ) -> PathBuf {
+        let data_path = self.data_path(path);
+        if ressources.backend.exists(&data_path).await.unwrap_or(true) {
+            return data_path;
+        }
+
+        #[cfg(feature = "compression")]
+        {
+            let compressed = data_path.with_added_extension("gz");
+            if ressources
+                .backend
+                .exists(&compressed)
+                .await
+                .unwrap_or(false)
+            {
+                return compressed;
+            }
+        }
+
+        let (Some(parent), Some(stem)) = (
+            data_path.parent(),
+            data_path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(self.data_extension.as_str())),
+        ) else {
+            return data_path;
+        };
+
+        if self.data_extension.is_empty() {
+            return data_path;
+        }
+
+        let Ok(mut entries) = ressources.backend.read_dir(parent).await else {
+            return data_path;
+        };
+
+        while let Some(Ok(entry)) = entries.next().await {
+            if entry
+                .to_str()
+                .and_then(|entry| entry.strip_prefix(stem))
+                .is_some_and(|extension| extension.eq_ignore_ascii_case(&self.data_extension))
+            {
+                return parent.join(entry);
+            }
+        }
+
+        data_path
+    }
+
+    /// Whether `data_path`, as returned by `find_data_path`, is a gzip-compressed copy of the
+    /// data rather than the plain file.
+    pub fn is_compressed_fallback(&self, path: &RessourcePath, data_path: &Path) -> bool {
+        cfg!(feature = "compression")
+            && *data_path == self.data_path(path).with_added_extension("gz")
+    }
+
+    pub fn backup_path(&self, path: &RessourcePath) -> PathBuf {
+        let mut backup_path = self.data_path(path);
+        backup_path.add_extension("bak");
+        backup_path
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            err,
+            fields(path = %ressources.metadata_path(path).display())
+        )
+    )]
+    pub async fn load<B: Backend>(
+        ressources: &Ressources<B>,
+        path: &RessourcePath,
+    ) -> RessourceResult<Self> {
+        Self::load_cached(ressources, path, &IndexCache::default()).await
+    }
+
+    pub(crate) async fn load_cached<B: Backend>(
+        ressources: &Ressources<B>,
+        path: &RessourcePath,
+        cache: &IndexCache,
+    ) -> RessourceResult<Self> {
+        let mut metadata: Self = serde_json::from_slice(
+            &ressources
+                .read_metadata_file_cached(path, cache)
+                .await
+                .map_err(|e| RessourceError::metadata_io(e, path))?,
+        )
+        .map_err(|e| RessourceError::metadata_format(e, path))?;
+
+        if metadata.schema_version > METADATA_SCHEMA_VERSION {
+            return Err(RessourceError::UnsupportedSchemaVersion {
+                ressource_path: path.clone(),
+                version: metadata.schema_version,
+                supported: METADATA_SCHEMA_VERSION,
+            });
+        }
+
+        if metadata.upgrade() && !ressources.read_only {
+            let bytes = ressources
+                .metadata_json(&metadata)
+                .map_err(|e| RessourceError::metadata_serialize(e, path))?;
+            ressources
+                .write_metadata_file(path, &bytes)
+                .await
+                .map_err(|e| RessourceError::write_metadata_io(e, path))?;
+        }
+
+        Ok(metadata)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct MetaRessource<T: RessourceType> {
     pub metadata: RessourceMetadata,
     pub path: RessourcePath,
@@ -26,21 +223,19 @@ pub struct MetaRessource<T: RessourceType> {
 }
 
 impl<T: RessourceType> MetaRessource<T> {
-    pub async fn load(path: RessourcePath) -> RessourceResult<Self> {
-        let metadata_path = path.metadata_path();
-        let metadata: RessourceMetadata =
-            serde_json::from_str(&read_to_string(&metadata_path).await.map_err(|e| {
-                RessourceError::MetadataIO {
-                    error: e,
-                    path: path.resolve(),
-                    ressource_path: path.clone(),
-                }
-            })?)
-            .map_err(|e| RessourceError::MetadataFormat {
-                error: e,
+    pub async fn load<B: Backend>(
+        ressources: &Ressources<B>,
+        path: RessourcePath,
+    ) -> RessourceResult<Self> {
+        let metadata = RessourceMetadata::load(ressources, &path).await?;
+
+        if path.path.last().is_some_and(|id| *id != metadata.id) {
+            return Err(RessourceError::IdMismatch {
+                metadata_id: metadata.id,
                 path: path.resolve(),
-                ressource_path: path.clone(),
-            })?;
+                ressource_path: path,
+            });
+        }
 
         if metadata.type_id != T::id() {
             return Err(RessourceError::TypeMismatch {
@@ -64,16 +259,17 @@ impl<T: RessourceType> MetaRessource<T> {
         let id = path
             .clone()
             .up()
-            .ok_or_else(|| RessourceError::RessourceAtRoot {
-                path: path.resolve(),
-                ressource_path: path.clone(),
-            })?;
+            .ok_or_else(|| RessourceError::ressource_at_root(&path))?;
 
         let metadata = RessourceMetadata {
             data_extension: T::data_extension().to_string(),
             type_id: T::id().to_string(),
             time: Utc::now(),
+            modified: None,
             id,
+            mime: T::mime_type().map(str::to_string),
+            extra: T::extra_metadata(),
+            schema_version: METADATA_SCHEMA_VERSION,
         };
 
         Ok(Self {
@@ -83,10 +279,198 @@ impl<T: RessourceType> MetaRessource<T> {
         })
     }
 
+    pub async fn touch<B: Backend>(&mut self, ressources: &Ressources<B>) -> RessourceResult<()> {
+        self.metadata.modified = Some(Utc::now());
+        self.write(ressources).await?;
+        ressources
+            .journal(JournalOperation::Update {
+                path: self.path.clone(),
+            })
+            .await
+    }
+
+    pub async fn write<B: Backend>(&self, ressources: &Ressources<B>) -> RessourceResult<()> {
+        ressources.check_writable(&self.path)?;
+        let metadata = ressources
+            .metadata_json(&self.metadata)
+            .map_err(|e| RessourceError::metadata_serialize(e, &self.path))?;
+
+        ressources
+            .write_metadata_file(&self.path, &metadata)
+            .await
+            .map_err(|e| RessourceError::write_metadata_io(e, &self.path))
+    }
+
+    pub async fn create<B: Backend>(&self, ressources: &Ressources<B>) -> RessourceResult<()> {
+        ressources.check_writable(&self.path)?;
+        let metadata = ressources
+            .metadata_json(&self.metadata)
+            .map_err(|e| RessourceError::metadata_serialize(e, &self.path))?;
+
+        match ressources.create_metadata_file(&self.path, &metadata).await {
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                Err(RessourceError::ressource_exists(&self.path))
+            }
+            result => result.map_err(|e| RessourceError::write_metadata_io(e, &self.path)),
+        }
+    }
+
     pub fn data_path(&self) -> PathBuf {
-        let mut path = self.path.resolve();
-        path.add_extension("data");
-        path.add_extension(self.metadata.data_extension.clone());
-        path
+        self.metadata.data_path(&self.path)
+    }
+
+    pub fn backup_path(&self) -> PathBuf {
+        self.metadata.backup_path(&self.path)
+    }
+
+    pub fn is_compressed_fallback(&self, data_path: &Path) -> bool {
+        self.metadata.is_compressed_fallback(&self.path, data_path)
+    }
+
+    pub async fn find_data_path<B: Backend>(&self, ressources: &Ressources<B>) -> PathBuf {
+        self.metadata.find_data_path(ressources, &self.path).await
+    }
+}
+
+impl MetaRessource<FolderRessource> {
+    pub fn root(root: PathBuf) -> Self {
+        let metadata = RessourceMetadata {
+            data_extension: FolderRessource::data_extension().to_string(),
+            type_id: FolderRessource::id().to_string(),
+            time: Utc::now(),
+            modified: None,
+            id: RessourceId::new(),
+            mime: None,
+            extra: serde_json::Map::new(),
+            schema_version: METADATA_SCHEMA_VERSION,
+        };
+
+        Self {
+            metadata,
+            path: RessourcePath::new(root),
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod tests {
+    use crate::{
+        backend::Backend, folder_ressource::FolderRessource, memory_backend::MemoryBackend,
+        ressource::Ressource, ressources::Ressources, text_ressource::TextRessource,
+    };
+    use futures::executor::block_on;
+    use std::path::Path;
+
+    #[test]
+    fn find_data_path_ignores_case_only_in_extension() {
+        block_on(async {
+            let ressources = Ressources::init_with_backend("/vault".into(), MemoryBackend::new())
+                .await
+                .unwrap();
+            for id in ["a", "Foo", "foo"] {
+                Ressource::new(
+                    &ressources,
+                    ressources.path(vec![id.into()]),
+                    TextRessource::new(id.into()),
+                )
+                .await
+                .unwrap();
+            }
+
+            ressources
+                .backend
+                .rename(
+                    Path::new("/vault/a.data.txt"),
+                    Path::new("/vault/a.data.TXT"),
+                )
+                .await
+                .unwrap();
+            let a =
+                Ressource::<TextRessource>::load(&ressources, ressources.path(vec!["a".into()]))
+                    .await
+                    .unwrap();
+            assert_eq!(a.data, TextRessource::new("a".into()));
+
+            ressources
+                .backend
+                .remove_file(Path::new("/vault/Foo.data.txt"))
+                .await
+                .unwrap();
+            assert!(
+                Ressource::<TextRessource>::load(&ressources, ressources.path(vec!["Foo".into()]))
+                    .await
+                    .is_err()
+            );
+        });
+    }
+
+    #[test]
+    fn reads_root_metadata_from_legacy_location() {
+        block_on(async {
+            let ressources = Ressources::init_with_backend("/vault".into(), MemoryBackend::new())
+                .await
+                .unwrap();
+            ressources
+                .backend
+                .rename(
+                    Path::new("/vault/.meta.json"),
+                    Path::new("/vault.meta.json"),
+                )
+                .await
+                .unwrap();
+
+            let root = ressources.path(Vec::new());
+            assert!(ressources.exists(&root).await.unwrap());
+            Ressource::<FolderRessource>::load(&ressources, root)
+                .await
+                .unwrap();
+        });
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn handles_compressed_fallback_in_read_range_and_append() {
+        use crate::error::RessourceError;
+        use flate2::{Compression, write::GzEncoder};
+        use std::io::Write;
+
+        block_on(async {
+            let ressources = Ressources::init_with_backend("/vault".into(), MemoryBackend::new())
+                .await
+                .unwrap();
+            let path = ressources.path(vec!["a".into()]);
+            Ressource::new(
+                &ressources,
+                path.clone(),
+                TextRessource::new("hello".into()),
+            )
+            .await
+            .unwrap();
+
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(b"hello").unwrap();
+            let backend = &ressources.backend;
+            backend
+                .write(
+                    Path::new("/vault/a.data.txt.gz"),
+                    &encoder.finish().unwrap(),
+                )
+                .await
+                .unwrap();
+            backend
+                .remove_file(Path::new("/vault/a.data.txt"))
+                .await
+                .unwrap();
+
+            let range = Ressource::<TextRessource>::read_range(&ressources, path.clone(), 1, 3)
+                .await
+                .unwrap();
+            assert_eq!(range, b"ell");
+            assert!(matches!(
+                ressources.append::<TextRessource>(path, b" world").await,
+                Err(RessourceError::AppendToCompressed { .. })
+            ));
+        });
     }
 }
@@ -0,0 +1,99 @@
+use crate::{
+    backend::{Backend, map_bytes},
+    traits::{ReadableRessource, RessourceType, WritableRessource},
+};
+use memmap2::Mmap;
+use std::{
+    ops::Deref,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum MmapRessourceError {
+    #[error("MmapRessource: IO Error mapping data at {path}. Error: {error}")]
+    Mapping {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+
+    #[error("MmapRessource: IO Error writing data at {path}. Error: {error}")]
+    Writing {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+/// Binary data read as a read-only memory map of the data file instead of a copy.
+///
+/// Updating or deleting the ressource through `Ressources` replaces the data file, so an
+/// existing map keeps seeing the old contents. Anything that modifies or truncates the
+/// file in place while it is mapped changes the bytes under the reader and may crash the
+/// process with `SIGBUS`, so only use this for files no one else writes to.
+#[derive(Debug)]
+pub struct MmapRessource {
+    pub map: Mmap,
+}
+
+impl MmapRessource {
+    pub fn from_bytes(data: &[u8]) -> std::io::Result<Self> {
+        map_bytes(data).map(|map| MmapRessource { map })
+    }
+}
+
+impl Deref for MmapRessource {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.map
+    }
+}
+
+impl RessourceType for MmapRessource {
+    fn id() -> &'static str {
+        "core/mmap"
+    }
+
+    fn display_name() -> &'static str {
+        "Memory mapped data"
+    }
+}
+
+impl ReadableRessource for MmapRessource {
+    type Error = MmapRessourceError;
+    async fn read<B: Backend>(backend: &B, path: &Path) -> Result<Self, MmapRessourceError> {
+        backend
+            .map(path)
+            .await
+            .map(|map| MmapRessource { map })
+            .map_err(|e| MmapRessourceError::Mapping {
+                path: path.to_path_buf(),
+                error: e,
+            })
+    }
+}
+
+impl WritableRessource for MmapRessource {
+    type Error = MmapRessourceError;
+    async fn write<B: Backend>(&self, backend: &B, path: &Path) -> Result<(), MmapRessourceError> {
+        backend
+            .write(path, &self.map)
+            .await
+            .map_err(|e| MmapRessourceError::Writing {
+                path: path.to_path_buf(),
+                error: e,
+            })
+    }
+
+    fn data_extension() -> &'static str {
+        "bin"
+    }
+
+    fn mime_type() -> Option<&'static str> {
+        Some("application/octet-stream")
+    }
+
+    fn estimated_size(&self) -> Option<u64> {
+        Some(self.map.len() as u64)
+    }
+}
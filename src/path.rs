@@ -1,33 +1,177 @@
-use std::{ffi::OsString, path::PathBuf};
+use serde::{Deserialize, Serialize};
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
 
 pub type RessourceId = String;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum NamingScheme {
+    #[default]
+    Suffix,
+    Hidden,
+    Index,
+}
+
+impl NamingScheme {
+    pub const DATA_SUFFIX: &'static str = ".data";
+    pub const METADATA_SUFFIX: &'static str = ".meta.json";
+    /// The root folder's metadata lives inside the root. Vaults created before this were
+    /// written to `<root>.meta.json` next to it, which is still read as a fallback.
+    pub const ROOT_METADATA: &'static str = ".meta.json";
+    pub const INDEX_FILE: &'static str = "_index.json";
+
+    pub fn folder_name(self, id: &str) -> String {
+        format!("{id}{}", Self::DATA_SUFFIX)
+    }
+
+    pub fn data_file_name(self, id: &str, extension: &str) -> String {
+        if extension.is_empty() {
+            return self.folder_name(id);
+        }
+
+        format!("{id}{}.{extension}", Self::DATA_SUFFIX)
+    }
+
+    pub fn metadata_file_name(self, id: &str) -> String {
+        match self {
+            NamingScheme::Suffix => format!("{id}{}", Self::METADATA_SUFFIX),
+            NamingScheme::Hidden => format!(".{id}{}", Self::METADATA_SUFFIX),
+            NamingScheme::Index => Self::INDEX_FILE.to_string(),
+        }
+    }
+
+    pub fn reserved_suffix(id: &str) -> Option<&'static str> {
+        [Self::METADATA_SUFFIX, Self::DATA_SUFFIX]
+            .into_iter()
+            .find(|suffix| id.ends_with(suffix))
+    }
+
+    pub fn id_from_folder_name(self, name: &str) -> Option<&str> {
+        name.strip_suffix(Self::DATA_SUFFIX)
+    }
+
+    pub fn id_from_metadata_file_name(self, name: &str) -> Option<&str> {
+        let id = match self {
+            NamingScheme::Suffix => name.strip_suffix(Self::METADATA_SUFFIX),
+            NamingScheme::Hidden => name
+                .strip_prefix('.')
+                .and_then(|name| name.strip_suffix(Self::METADATA_SUFFIX)),
+            NamingScheme::Index => None,
+        }?;
+
+        (!id.is_empty()).then_some(id)
+    }
+
+    pub fn metadata_path(self, path: &RessourcePath) -> PathBuf {
+        if path.path.is_empty() {
+            return path.root.join(Self::ROOT_METADATA);
+        }
+
+        self.file_path(path, |id| self.metadata_file_name(id))
+    }
+
+    pub fn legacy_root_metadata_path(root: &Path) -> PathBuf {
+        root.with_added_extension("meta.json")
+    }
+
+    pub fn data_path(self, path: &RessourcePath, extension: &str) -> PathBuf {
+        if path.path.is_empty() {
+            return path.resolve();
+        }
+
+        self.file_path(path, |id| self.data_file_name(id, extension))
+    }
+
+    fn file_path(self, path: &RessourcePath, file_name: impl Fn(&str) -> String) -> PathBuf {
+        let mut res = match path.with_parent() {
+            Some(parent) => parent.resolve_folder(),
+            None => path.root.clone(),
+        };
+        res.push(file_name(path.path.last().map_or("", String::as_str)));
+        res
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct RessourcePath {
     pub path: Vec<RessourceId>,
+    #[serde(skip)]
     pub root: PathBuf,
 }
 
 impl std::fmt::Display for RessourcePath {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.path.join("/"))
+        if self.path.is_empty() {
+            return f.write_str("<root>");
+        }
+
+        for (i, id) in self.components_iter().enumerate() {
+            if i != 0 {
+                f.write_str("/")?;
+            }
+            f.write_str(id)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<RessourcePath> for String {
+    fn from(value: RessourcePath) -> Self {
+        value.to_id_string()
     }
 }
 
 impl RessourcePath {
+    pub fn is_valid_id(id: &str) -> bool {
+        !id.is_empty()
+            && id != "."
+            && id != ".."
+            && !id.contains('/')
+            && !id.contains(std::path::MAIN_SEPARATOR)
+    }
+
+    pub fn components_iter(&self) -> impl Iterator<Item = &RessourceId> {
+        self.path.iter()
+    }
+
+    pub fn to_id_string(&self) -> String {
+        self.path.join("/")
+    }
+
     pub fn resolve(&self) -> PathBuf {
-        let mut res = self.root.clone();
-        for (i, id) in self.path.iter().enumerate() {
-            let cmppath = if i == self.path.len() - 1 {
-                id.clone()
-            } else {
-                format!("{id}.data")
-            };
-            res.push(std::path::Component::Normal(&OsString::from(cmppath)));
-        }
+        let mut res = PathBuf::new();
+        self.resolve_into(&mut res);
         res
     }
 
+    pub fn relative_os_path(&self) -> PathBuf {
+        self.with_root(PathBuf::new()).resolve()
+    }
+
+    pub fn resolve_into(&self, buf: &mut PathBuf) {
+        buf.clear();
+        buf.push(&self.root);
+        if let Some((id, folders)) = self.path.split_last() {
+            Self::push_folders(buf, folders);
+            buf.push(std::path::Component::Normal(OsStr::new(id)));
+        }
+    }
+
+    fn resolve_folder(&self) -> PathBuf {
+        let mut buf = self.root.clone();
+        Self::push_folders(&mut buf, &self.path);
+        buf
+    }
+
+    fn push_folders(buf: &mut PathBuf, folders: &[RessourceId]) {
+        for id in folders {
+            buf.push(std::path::Component::Normal(OsStr::new(id)));
+            buf.as_mut_os_string().push(NamingScheme::DATA_SUFFIX);
+        }
+    }
+
     pub fn push(&mut self, component: impl Into<RessourceId>) {
         self.path.push(component.into());
     }
@@ -57,16 +201,35 @@ impl RessourcePath {
         other.up().map(|_v| other)
     }
 
+    pub fn ancestors(&self) -> Vec<RessourcePath> {
+        let mut ancestors = vec![self.clone()];
+        let mut current = self.clone();
+        while current.up().is_some() {
+            ancestors.push(current.clone());
+        }
+        ancestors
+    }
+
+    pub fn is_top_level(&self) -> bool {
+        self.path.len() == 1
+    }
+
+    /// Metadata file under the default naming scheme; `Ressources::metadata_path` follows the vault's scheme.
     pub fn metadata_path(&self) -> PathBuf {
-        let mut path = self.resolve();
-        path.add_extension("meta.json");
-        path
+        NamingScheme::default().metadata_path(self)
     }
 
     pub fn from_vec(root: PathBuf, path: Vec<RessourceId>) -> Self {
         RessourcePath { path, root }
     }
 
+    pub fn with_root(&self, root: PathBuf) -> RessourcePath {
+        RessourcePath {
+            path: self.path.clone(),
+            root,
+        }
+    }
+
     pub fn new(root: PathBuf) -> Self {
         RessourcePath {
             path: Vec::new(),
@@ -74,3 +237,124 @@ impl RessourcePath {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMES: [NamingScheme; 3] = [
+        NamingScheme::Suffix,
+        NamingScheme::Hidden,
+        NamingScheme::Index,
+    ];
+
+    fn path(ids: &[&str]) -> RessourcePath {
+        RessourcePath::from_vec(
+            PathBuf::from("/vault"),
+            ids.iter().map(|id| id.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn root_paths() {
+        for naming in SCHEMES {
+            let root = path(&[]);
+            assert_eq!(
+                naming.metadata_path(&root),
+                PathBuf::from("/vault/.meta.json")
+            );
+            assert_eq!(naming.data_path(&root, ""), PathBuf::from("/vault"));
+            assert_eq!(naming.data_path(&root, "txt"), PathBuf::from("/vault"));
+        }
+    }
+
+    #[test]
+    fn metadata_paths() {
+        let expected = [
+            (
+                NamingScheme::Suffix,
+                "/vault/a.meta.json",
+                "/vault/f.data/g.data/a.meta.json",
+            ),
+            (
+                NamingScheme::Hidden,
+                "/vault/.a.meta.json",
+                "/vault/f.data/g.data/.a.meta.json",
+            ),
+            (
+                NamingScheme::Index,
+                "/vault/_index.json",
+                "/vault/f.data/g.data/_index.json",
+            ),
+        ];
+
+        for (naming, top_level, nested) in expected {
+            assert_eq!(
+                naming.metadata_path(&path(&["a"])),
+                PathBuf::from(top_level)
+            );
+            assert_eq!(
+                naming.metadata_path(&path(&["f", "g", "a"])),
+                PathBuf::from(nested)
+            );
+        }
+    }
+
+    #[test]
+    fn data_paths() {
+        for naming in SCHEMES {
+            let top_level = path(&["a"]);
+            let nested = path(&["f", "g", "a"]);
+            assert_eq!(
+                naming.data_path(&top_level, "txt"),
+                PathBuf::from("/vault/a.data.txt")
+            );
+            assert_eq!(
+                naming.data_path(&nested, "txt"),
+                PathBuf::from("/vault/f.data/g.data/a.data.txt")
+            );
+            assert_eq!(
+                naming.data_path(&nested, ""),
+                PathBuf::from("/vault/f.data/g.data/a.data")
+            );
+        }
+    }
+
+    #[test]
+    fn metadata_file_names_round_trip() {
+        for naming in [NamingScheme::Suffix, NamingScheme::Hidden] {
+            for ids in [&["a"][..], &["f", "g", "a.b"], &["f", ".a"]] {
+                let path = path(ids);
+                let metadata_path = naming.metadata_path(&path);
+                let file_name = metadata_path.file_name().and_then(OsStr::to_str).unwrap();
+                assert_eq!(
+                    naming.id_from_metadata_file_name(file_name),
+                    path.path.last().map(String::as_str)
+                );
+            }
+
+            assert_eq!(
+                naming.id_from_metadata_file_name(NamingScheme::ROOT_METADATA),
+                None
+            );
+            assert_eq!(naming.id_from_metadata_file_name("a.data.txt"), None);
+        }
+
+        assert_eq!(
+            NamingScheme::Index.id_from_metadata_file_name(NamingScheme::INDEX_FILE),
+            None
+        );
+    }
+
+    #[test]
+    fn folder_names_round_trip() {
+        for naming in SCHEMES {
+            for id in ["a", "a.b", ".a"] {
+                assert_eq!(
+                    naming.id_from_folder_name(&naming.folder_name(id)),
+                    Some(id)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use crate::{
+    backend::Backend,
+    error::{RessourceError, RessourceResult},
+    folder_ressource::FolderRessource,
+    journal::JournalOperation,
+    meta::{MetaRessource, RessourceMetadata},
+    path::RessourcePath,
+    ressources::Ressources,
+    traits::RessourceType,
+};
+use std::io;
+
+impl<B: Backend> Ressources<B> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            err,
+            fields(from = %from.resolve().display(), to = %to.resolve().display())
+        )
+    )]
+    pub async fn move_folder(
+        &self,
+        from: RessourcePath,
+        to: RessourcePath,
+    ) -> RessourceResult<RessourcePath> {
+        self.check_move(&from, &to).await?;
+        MetaRessource::<FolderRessource>::load(self, from.clone()).await?;
+        self.relocate(from, to).await
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            err,
+            fields(from = %ressource.resolve().display(), folder = %folder.resolve().display())
+        )
+    )]
+    pub async fn move_into(
+        &self,
+        ressource: RessourcePath,
+        folder: RessourcePath,
+    ) -> RessourceResult<RessourcePath> {
+        let id = ressource
+            .path
+            .last()
+            .cloned()
+            .ok_or_else(|| RessourceError::ressource_at_root(&ressource))?;
+        let to = folder.with_child(id);
+        self.check_move(&ressource, &to).await?;
+        self.relocate(ressource, to).await
+    }
+
+    async fn relocate(
+        &self,
+        from: RessourcePath,
+        to: RessourcePath,
+    ) -> RessourceResult<RessourcePath> {
+        let mut metadata = RessourceMetadata::load(self, &from).await?;
+        let data_path = metadata.data_path(&from);
+        let backup_path = metadata.backup_path(&from);
+        metadata.id = to.path.last().cloned().unwrap_or_default();
+        let new_data_path = metadata.data_path(&to);
+
+        match self.backend.rename(&data_path, &new_data_path).await {
+            Ok(()) => {
+                let written = match self.metadata_json(&metadata) {
+                    Ok(bytes) => self
+                        .write_metadata_file(&to, &bytes)
+                        .await
+                        .map_err(|e| RessourceError::write_metadata_io(e, &to)),
+                    Err(e) => Err(RessourceError::metadata_serialize(e, &to)),
+                };
+                if let Err(e) = written {
+                    let _ = self.backend.rename(&new_data_path, &data_path).await;
+                    return Err(e);
+                }
+
+                self.remove_metadata_file(&from)
+                    .await
+                    .map_err(|e| RessourceError::delete_io(e, &from, &self.metadata_path(&from)))?;
+
+                let _ = self
+                    .backend
+                    .rename(&backup_path, &metadata.backup_path(&to))
+                    .await;
+            }
+            Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
+                self.copy_tree(&from, &to).await?;
+                let metadata = RessourceMetadata::load(self, &from).await?;
+                self.remove_tree(&from, metadata).await?;
+            }
+            Err(e) => {
+                return Err(RessourceError::rename_io(
+                    e,
+                    &from,
+                    &data_path,
+                    &new_data_path,
+                ));
+            }
+        }
+
+        let from_metadata_path = self.metadata_path(&from);
+        let to_metadata_path = self.metadata_path(&to);
+        let files: Vec<_> = [from_metadata_path.parent(), to_metadata_path.parent()]
+            .into_iter()
+            .flatten()
+            .collect();
+        self.sync(&to, &files).await?;
+        self.journal(JournalOperation::Move {
+            from,
+            to: to.clone(),
+        })
+        .await?;
+
+        Ok(to)
+    }
+
+    async fn check_move(&self, from: &RessourcePath, to: &RessourcePath) -> RessourceResult<()> {
+        self.check_writable(from)?;
+        for path in [from, to] {
+            if path.path.is_empty() {
+                return Err(RessourceError::ressource_at_root(path));
+            }
+        }
+
+        if to.path.starts_with(&from.path) {
+            return Err(RessourceError::MoveIntoSelf {
+                ressource_path: from.clone(),
+                target: to.clone(),
+            });
+        }
+
+        self.check_id(to)?;
+        if self.exists(to).await? {
+            return Err(RessourceError::ressource_exists(to));
+        }
+
+        self.check_parent(to).await
+    }
+
+    async fn copy_tree(&self, from: &RessourcePath, to: &RessourcePath) -> RessourceResult<()> {
+        let metadata = RessourceMetadata::load(self, from).await?;
+        let is_folder = metadata.type_id == FolderRessource::id();
+        let mut entries = vec![(from.clone(), metadata)];
+        if is_folder {
+            entries.extend(self.walk(from.clone()).await?);
+        }
+
+        for (entry, mut metadata) in entries {
+            let source_data_path = metadata.data_path(&entry);
+            let source_backup_path = metadata.backup_path(&entry);
+            let mut target = to.clone();
+            target
+                .path
+                .extend_from_slice(&entry.path[from.path.len()..]);
+            metadata.id = target.path.last().cloned().unwrap_or_default();
+            let target_data_path = metadata.data_path(&target);
+
+            let copied = if metadata.type_id == FolderRessource::id() {
+                self.backend.create_dir(&target_data_path).await
+            } else {
+                match self.backend.read(&source_data_path).await {
+                    Ok(data) => self.backend.write(&target_data_path, &data).await,
+                    Err(e) => {
+                        return Err(RessourceError::read_data_io(e, &entry, &source_data_path));
+                    }
+                }
+            };
+            copied.map_err(|e| RessourceError::write_data_io(e, &target, &target_data_path))?;
+
+            let read_backup = |e| RessourceError::read_data_io(e, &entry, &source_backup_path);
+            if self
+                .backend
+                .exists(&source_backup_path)
+                .await
+                .map_err(read_backup)?
+            {
+                let backup = self
+                    .backend
+                    .read(&source_backup_path)
+                    .await
+                    .map_err(read_backup)?;
+                let target_backup_path = metadata.backup_path(&target);
+                self.backend
+                    .write(&target_backup_path, &backup)
+                    .await
+                    .map_err(|e| RessourceError::write_data_io(e, &target, &target_backup_path))?;
+            }
+
+            let metadata = self
+                .metadata_json(&metadata)
+                .map_err(|e| RessourceError::metadata_serialize(e, &target))?;
+            self.write_metadata_file(&target, &metadata)
+                .await
+                .map_err(|e| RessourceError::write_metadata_io(e, &target))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod tests {
+    use crate::{
+        backend::Backend, folder_ressource::FolderRessource, memory_backend::MemoryBackend,
+        ressource::Ressource, ressources::Ressources, text_ressource::TextRessource,
+    };
+    use futures::executor::block_on;
+    use std::path::Path;
+
+    async fn assert_files(
+        ressources: &Ressources<MemoryBackend>,
+        dir: &str,
+        id: &str,
+        exist: bool,
+    ) {
+        for name in [
+            format!("{id}.meta.json"),
+            format!("{id}.data.txt"),
+            format!("{id}.data.txt.bak"),
+        ] {
+            let path = Path::new(dir).join(name);
+            assert_eq!(
+                ressources.backend.exists(&path).await.unwrap(),
+                exist,
+                "{}",
+                path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn rename_and_move_carry_data_and_backup() {
+        block_on(async {
+            let mut ressources =
+                Ressources::init_with_backend("/vault".into(), MemoryBackend::new())
+                    .await
+                    .unwrap();
+            ressources.backups = true;
+
+            let path = ressources.path(vec!["a".into()]);
+            let folder = ressources.path(vec!["f".into()]);
+            Ressource::new(&ressources, folder.clone(), FolderRessource::new())
+                .await
+                .unwrap();
+            Ressource::new(&ressources, path.clone(), TextRessource::new("old".into()))
+                .await
+                .unwrap();
+            Ressource::update(&ressources, path.clone(), TextRessource::new("new".into()))
+                .await
+                .unwrap();
+            assert_files(&ressources, "/vault", "a", true).await;
+
+            let renamed = Ressource::<TextRessource>::rename(&ressources, path, "b".into())
+                .await
+                .unwrap();
+            assert_files(&ressources, "/vault", "a", false).await;
+            assert_files(&ressources, "/vault", "b", true).await;
+
+            let moved = ressources.move_into(renamed, folder).await.unwrap();
+            assert_files(&ressources, "/vault", "b", false).await;
+            assert_files(&ressources, "/vault/f.data", "b", true).await;
+
+            let current = Ressource::<TextRessource>::load(&ressources, moved.clone())
+                .await
+                .unwrap();
+            let previous = Ressource::<TextRessource>::load_previous(&ressources, moved)
+                .await
+                .unwrap();
+            assert_eq!(current.meta.metadata.id, "b");
+            assert_eq!(current.data, TextRessource::new("new".into()));
+            assert_eq!(previous.data, TextRessource::new("old".into()));
+        });
+    }
+
+    #[test]
+    fn copy_tree_carries_backups() {
+        block_on(async {
+            let mut ressources =
+                Ressources::init_with_backend("/vault".into(), MemoryBackend::new())
+                    .await
+                    .unwrap();
+            ressources.backups = true;
+
+            let folder = ressources.path(vec!["f".into()]);
+            let path = folder.with_child("a");
+            Ressource::new(&ressources, folder.clone(), FolderRessource::new())
+                .await
+                .unwrap();
+            Ressource::new(&ressources, path.clone(), TextRessource::new("old".into()))
+                .await
+                .unwrap();
+            Ressource::update(&ressources, path, TextRessource::new("new".into()))
+                .await
+                .unwrap();
+
+            let target = ressources.path(vec!["g".into()]);
+            ressources.copy_tree(&folder, &target).await.unwrap();
+            assert_files(&ressources, "/vault/g.data", "a", true).await;
+
+            let previous =
+                Ressource::<TextRessource>::load_previous(&ressources, target.with_child("a"))
+                    .await
+                    .unwrap();
+            assert_eq!(previous.data, TextRessource::new("old".into()));
+        });
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{
+    backend::Backend,
+    error::{RessourceError, RessourceResult},
+    journal::JournalOperation,
+    meta::MetaRessource,
+    path::{RessourceId, RessourcePath},
+    ressource::Ressource,
+    ressources::Ressources,
+    traits::WritableRessource,
+};
+
+pub const INCOMPLETE_KEY: &str = "incomplete";
+
+#[derive(Debug)]
+pub struct Reservation<'a, B: Backend, T: WritableRessource> {
+    ressources: &'a Ressources<B>,
+    meta: MetaRessource<T>,
+}
+
+impl<'a, B: Backend, T: WritableRessource> Reservation<'a, B, T> {
+    pub fn path(&self) -> &RessourcePath {
+        &self.meta.path
+    }
+
+    pub async fn commit(mut self, data: T) -> RessourceResult<Ressource<T>> {
+        let path = self.meta.path.clone();
+        let data_path = self.meta.data_path();
+        Ressource::write_data(self.ressources, &path, &data, &data_path).await?;
+
+        self.meta.metadata.extra.remove(INCOMPLETE_KEY);
+        self.meta.metadata.mime = data.mime().map(str::to_string);
+        self.meta.write(self.ressources).await?;
+
+        let metadata_path = self.ressources.metadata_path(&path);
+        self.ressources
+            .sync(&path, &[data_path.as_path(), metadata_path.as_path()])
+            .await?;
+        self.ressources
+            .journal(JournalOperation::Create { path })
+            .await?;
+
+        Ok(Ressource {
+            data,
+            meta: self.meta,
+        })
+    }
+
+    pub async fn abort(self) -> RessourceResult<()> {
+        self.ressources
+            .remove_metadata_file(&self.meta.path)
+            .await
+            .map_err(|e| {
+                RessourceError::delete_io(
+                    e,
+                    &self.meta.path,
+                    &self.ressources.metadata_path(&self.meta.path),
+                )
+            })
+    }
+}
+
+impl<B: Backend> Ressources<B> {
+    pub async fn reserve<T: WritableRessource>(
+        &self,
+        parent: &RessourcePath,
+        id: RessourceId,
+    ) -> RessourceResult<Reservation<'_, B, T>> {
+        let path = parent.with_child(id);
+        self.check_id(&path)?;
+        self.check_type(&path, T::id())?;
+        self.check_parent(&path).await?;
+        let mut meta = MetaRessource::<T>::new(path)?;
+        meta.metadata
+            .extra
+            .insert(INCOMPLETE_KEY.to_string(), serde_json::Value::Bool(true));
+        meta.create(self).await?;
+
+        Ok(Reservation {
+            ressources: self,
+            meta,
+        })
+    }
+}
@@ -1,30 +1,208 @@
+use chrono::{DateTime, Utc};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::backend::Backend;
 use crate::error::{RessourceError, RessourceResult, WriteDataError};
 use crate::folder_ressource::FolderRessource;
+use crate::journal::JournalOperation;
 use crate::meta::MetaRessource;
-use crate::path::RessourcePath;
+use crate::path::{RessourceId, RessourcePath};
+use crate::ressources::Ressources;
 use crate::traits::{ReadableRessource, RessourceType, WritableRessource};
-use tokio::fs;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Ressource<T: RessourceType> {
     pub data: T,
     pub meta: MetaRessource<T>,
 }
 
+#[derive(Debug, Clone)]
+pub struct LazyRessource<T: RessourceType> {
+    pub meta: MetaRessource<T>,
+}
+
+impl<T: RessourceType> LazyRessource<T> {
+    pub async fn load<B: Backend>(
+        ressources: &Ressources<B>,
+        path: RessourcePath,
+    ) -> RessourceResult<Self> {
+        Ok(LazyRessource {
+            meta: MetaRessource::<T>::load(ressources, path).await?,
+        })
+    }
+
+    pub async fn load_data<B: Backend>(
+        self,
+        ressources: &Ressources<B>,
+    ) -> RessourceResult<Ressource<T>>
+    where
+        T: ReadableRessource,
+    {
+        let data_path = self.meta.find_data_path(ressources).await;
+        let data = self
+            .read_data(ressources, &data_path)
+            .await
+            .map_err(|e| RessourceError::invalid_data(T::id(), &self.meta.path, e))?;
+
+        Ok(Ressource {
+            data,
+            meta: self.meta,
+        })
+    }
+
+    async fn read_data<B: Backend>(
+        &self,
+        ressources: &Ressources<B>,
+        data_path: &Path,
+    ) -> Result<T, Box<dyn std::error::Error>>
+    where
+        T: ReadableRessource,
+    {
+        #[cfg(feature = "compression")]
+        if self.meta.is_compressed_fallback(data_path) {
+            return Ok(crate::compressed::read_gzip(&ressources.backend, data_path).await?);
+        }
+
+        Ok(T::read_in(ressources, data_path).await?)
+    }
+}
+
 impl<T: RessourceType> Ressource<T> {
-    pub async fn load(path: RessourcePath) -> RessourceResult<Self>
+    pub fn id(&self) -> &RessourceId {
+        &self.meta.metadata.id
+    }
+
+    pub fn created(&self) -> DateTime<Utc> {
+        self.meta.metadata.time
+    }
+
+    pub fn type_id(&self) -> &str {
+        &self.meta.metadata.type_id
+    }
+
+    pub fn mime(&self) -> Option<&str> {
+        self.meta.metadata.mime.as_deref()
+    }
+
+    pub fn data_os_path(&self) -> PathBuf {
+        self.meta.data_path()
+    }
+
+    pub fn metadata_os_path<B: Backend>(&self, ressources: &Ressources<B>) -> PathBuf {
+        ressources.metadata_path(&self.meta.path)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            err,
+            fields(ressource_type = T::id(), path = %path.resolve().display())
+        )
+    )]
+    pub async fn load<B: Backend>(
+        ressources: &Ressources<B>,
+        path: RessourcePath,
+    ) -> RessourceResult<Self>
+    where
+        T: ReadableRessource,
+    {
+        LazyRessource::<T>::load(ressources, path)
+            .await?
+            .load_data(ressources)
+            .await
+    }
+
+    pub async fn load_timeout<B: Backend>(
+        ressources: &Ressources<B>,
+        path: RessourcePath,
+        timeout: Duration,
+    ) -> RessourceResult<Self>
     where
         T: ReadableRessource,
     {
-        let meta_ressource = MetaRessource::<T>::load(path.clone()).await?;
-        let data = T::read(&meta_ressource.data_path()).await.map_err(|e| {
-            RessourceError::InvalidData {
-                ressource_type: T::id(),
-                path: path.resolve(),
-                ressource_path: path.clone(),
-                error: Box::new(e),
+        match tokio::time::timeout(timeout, Self::load(ressources, path.clone())).await {
+            Ok(result) => result,
+            Err(_) => Err(RessourceError::timeout(&path, timeout)),
+        }
+    }
+
+    pub async fn load_or_create<B: Backend>(
+        ressources: &Ressources<B>,
+        path: RessourcePath,
+        create: impl FnOnce() -> T,
+    ) -> RessourceResult<Self>
+    where
+        T: ReadableRessource + WritableRessource,
+    {
+        match Self::load(ressources, path.clone()).await {
+            Err(RessourceError::MetadataIO { error, .. })
+                if error.kind() == io::ErrorKind::NotFound =>
+            {
+                Self::new(ressources, path, create()).await
             }
-        })?;
+            result => result,
+        }
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            err,
+            fields(ressource_type = T::id(), legacy_type = L::id(), path = %path.resolve().display())
+        )
+    )]
+    pub async fn load_with_migration<B: Backend, L: ReadableRessource>(
+        ressources: &Ressources<B>,
+        path: RessourcePath,
+        migrate: impl FnOnce(L) -> T,
+    ) -> RessourceResult<Self>
+    where
+        T: ReadableRessource + WritableRessource,
+    {
+        match Self::load(ressources, path.clone()).await {
+            Err(RessourceError::TypeMismatch { .. }) => {}
+            result => return result,
+        }
+
+        ressources.check_writable(&path)?;
+        ressources.check_type(&path, T::id())?;
+        let legacy = Ressource::<L>::load(ressources, path.clone()).await?;
+        let legacy_data_path = legacy.meta.find_data_path(ressources).await;
+        let data = migrate(legacy.data);
+
+        let mut meta_ressource = MetaRessource::<T>::new(path.clone())?;
+        let mut extra = legacy.meta.metadata.extra;
+        extra.extend(meta_ressource.metadata.extra);
+        meta_ressource.metadata.extra = extra;
+        meta_ressource.metadata.time = legacy.meta.metadata.time;
+        meta_ressource.metadata.modified = Some(Utc::now());
+        meta_ressource.metadata.mime = data.mime().map(str::to_string);
+
+        let data_path = meta_ressource.data_path();
+        Self::write_data(ressources, &path, &data, &data_path).await?;
+        meta_ressource.write(ressources).await?;
+
+        if legacy_data_path != data_path {
+            ressources
+                .backend
+                .remove_file(&legacy_data_path)
+                .await
+                .map_err(|e| RessourceError::delete_io(e, &path, &legacy_data_path))?;
+        }
+
+        let metadata_path = ressources.metadata_path(&path);
+        ressources
+            .sync(&path, &[data_path.as_path(), metadata_path.as_path()])
+            .await?;
+        ressources
+            .journal(JournalOperation::Update { path })
+            .await?;
 
         Ok(Ressource {
             data,
@@ -32,61 +210,308 @@ impl<T: RessourceType> Ressource<T> {
         })
     }
 
-    pub async fn new(path: RessourcePath, data: T) -> RessourceResult<Self>
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            err,
+            fields(ressource_type = T::id(), path = %path.resolve().display())
+        )
+    )]
+    pub async fn new<B: Backend>(
+        ressources: &Ressources<B>,
+        path: RessourcePath,
+        data: T,
+    ) -> RessourceResult<Self>
     where
         T: WritableRessource,
     {
-        let meta_ressource = MetaRessource::new(path.clone())?;
-        let mut parent_ressource = path.clone();
-        parent_ressource
-            .up()
-            .ok_or_else(|| RessourceError::RessourceAtRoot {
-                path: path.resolve(),
-                ressource_path: path.clone(),
-            })?;
-
-        if !parent_ressource.path.is_empty() {
-            Ressource::<FolderRessource>::load(parent_ressource)
-                .await
-                .map_err(|e| RessourceError::ParentRessource {
-                    path: path.resolve(),
-                    ressource_path: path.clone(),
-                    folder_error: Box::new(e),
-                })?;
+        let meta = Self::create(ressources, path, &data).await?;
+        Ok(Ressource { data, meta })
+    }
+
+    pub(crate) async fn create<B: Backend>(
+        ressources: &Ressources<B>,
+        path: RessourcePath,
+        data: &T,
+    ) -> RessourceResult<MetaRessource<T>>
+    where
+        T: WritableRessource,
+    {
+        ressources.check_writable(&path)?;
+        ressources.check_id(&path)?;
+        ressources.check_type(&path, T::id())?;
+        let mut meta_ressource = MetaRessource::new(path.clone())?;
+        meta_ressource.metadata.mime = data.mime().map(str::to_string);
+        ressources.check_parent(&path).await?;
+        meta_ressource.create(ressources).await?;
+
+        let data_path = meta_ressource.data_path();
+        if let Err(e) = Self::write_data(ressources, &path, data, &data_path).await {
+            return match (ressources.remove_metadata_file(&path).await, e) {
+                (Err(error), RessourceError::WriteDataError(data_error)) => {
+                    Err(RessourceError::DeleteMetadataError { data_error, error })
+                }
+                (_, e) => Err(e),
+            };
+        }
+
+        let metadata_path = ressources.metadata_path(&path);
+        let mut files = vec![data_path.as_path(), metadata_path.as_path()];
+        if let Some(parent) = metadata_path.parent() {
+            files.push(parent);
         }
+        ressources.sync(&path, &files).await?;
+        ressources
+            .journal(JournalOperation::Create { path: path.clone() })
+            .await?;
+
+        Ok(meta_ressource)
+    }
+
+    pub(crate) async fn write_data<B: Backend>(
+        ressources: &Ressources<B>,
+        path: &RessourcePath,
+        data: &T,
+        data_path: &Path,
+    ) -> RessourceResult<()>
+    where
+        T: WritableRessource,
+    {
+        if let (Some(limit), Some(size)) = (ressources.max_data_bytes, data.estimated_size())
+            && size > limit
+        {
+            return Err(RessourceError::data_too_large(path, limit));
+        }
+
+        ressources
+            .replace_data(
+                path,
+                data_path,
+                async |backend, temp_path| data.write(backend, temp_path).await,
+                |e| WriteDataError::new(T::id(), path, e).into(),
+            )
+            .await
+    }
 
-        fs::write(
-            path.metadata_path(),
-            serde_json::to_string(&meta_ressource.metadata).unwrap(),
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            err,
+            fields(ressource_type = T::id(), path = %path.resolve().display())
         )
-        .await
-        .map_err(|e| RessourceError::WriteMetadataIO {
-            error: e,
-            ressource_path: path.clone(),
-            path: path.resolve(),
-        })?;
+    )]
+    pub async fn update<B: Backend>(
+        ressources: &Ressources<B>,
+        path: RessourcePath,
+        data: T,
+    ) -> RessourceResult<Self>
+    where
+        T: WritableRessource,
+    {
+        ressources.check_writable(&path)?;
+        if T::id() == FolderRessource::id() {
+            return Err(RessourceError::FolderUpdate {
+                ressource_path: path,
+            });
+        }
 
+        let mut meta_ressource = MetaRessource::<T>::load(ressources, path.clone()).await?;
         let data_path = meta_ressource.data_path();
-        if let Err(write_data_error) = data.write(&data_path).await.map_err(|e| WriteDataError {
-            ressource_type: T::id(),
-            ressource_path: path.clone(),
-            path: path.resolve(),
-            error: Box::new(e),
-        }) {
-            match fs::remove_file(path.metadata_path()).await {
-                Ok(_) => return Err(RessourceError::WriteDataError(write_data_error)),
-                Err(e) => {
-                    return Err(RessourceError::DeleteMetadataError {
-                        data_error: write_data_error,
-                        error: e,
-                    });
+        let backup_path = meta_ressource.backup_path();
+
+        if ressources.backups {
+            match ressources.backend.rename(&data_path, &backup_path).await {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                    return Err(RessourceError::rename_io(
+                        e,
+                        &path,
+                        &data_path,
+                        &backup_path,
+                    ));
                 }
+                _ => {}
+            }
+        }
+
+        if let Err(e) = Self::write_data(ressources, &path, &data, &data_path).await {
+            if ressources.backups {
+                let _ = ressources.backend.rename(&backup_path, &data_path).await;
             }
+            return Err(e);
         }
 
+        meta_ressource.metadata.modified = Some(Utc::now());
+        meta_ressource.metadata.mime = data.mime().map(str::to_string);
+        meta_ressource.write(ressources).await?;
+
+        let metadata_path = ressources.metadata_path(&path);
+        ressources
+            .sync(&path, &[data_path.as_path(), metadata_path.as_path()])
+            .await?;
+        ressources
+            .journal(JournalOperation::Update { path })
+            .await?;
+
+        Ok(Ressource {
+            data,
+            meta: meta_ressource,
+        })
+    }
+
+    /// Loads the single `.bak` backup written by the last `update`; older versions are not kept.
+    pub async fn load_previous<B: Backend>(
+        ressources: &Ressources<B>,
+        path: RessourcePath,
+    ) -> RessourceResult<Self>
+    where
+        T: ReadableRessource,
+    {
+        let meta_ressource = MetaRessource::<T>::load(ressources, path.clone()).await?;
+        let data = T::read_in(ressources, &meta_ressource.backup_path())
+            .await
+            .map_err(|e| RessourceError::invalid_data(T::id(), &path, Box::new(e)))?;
+
         Ok(Ressource {
             data,
             meta: meta_ressource,
         })
     }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            err,
+            fields(ressource_type = T::id(), path = %path.resolve().display(), new_id = %new_id)
+        )
+    )]
+    pub async fn rename<B: Backend>(
+        ressources: &Ressources<B>,
+        path: RessourcePath,
+        new_id: RessourceId,
+    ) -> RessourceResult<RessourcePath> {
+        if !RessourcePath::is_valid_id(&new_id) {
+            return Err(RessourceError::invalid_ressource_id(&new_id, &path));
+        }
+
+        ressources.check_writable(&path)?;
+        let mut meta_ressource = MetaRessource::<T>::load(ressources, path.clone()).await?;
+        let mut new_path = path
+            .with_parent()
+            .ok_or_else(|| RessourceError::ressource_at_root(&path))?;
+        new_path.push(new_id.clone());
+        ressources.check_id(&new_path)?;
+
+        if ressources.exists(&new_path).await? {
+            return Err(RessourceError::ressource_exists(&new_path));
+        }
+
+        let data_path = meta_ressource.data_path();
+        let backup_path = meta_ressource.backup_path();
+        meta_ressource.path = new_path.clone();
+        meta_ressource.metadata.id = new_id;
+        let new_data_path = meta_ressource.data_path();
+
+        ressources
+            .backend
+            .rename(&data_path, &new_data_path)
+            .await
+            .map_err(|e| RessourceError::rename_io(e, &path, &data_path, &new_data_path))?;
+
+        if let Err(e) = meta_ressource.write(ressources).await {
+            let _ = ressources.backend.rename(&new_data_path, &data_path).await;
+            return Err(e);
+        }
+
+        if let Err(e) = ressources.remove_metadata_file(&path).await {
+            let _ = ressources.remove_metadata_file(&new_path).await;
+            let _ = ressources.backend.rename(&new_data_path, &data_path).await;
+            return Err(RessourceError::rename_io(
+                e,
+                &path,
+                &ressources.metadata_path(&path),
+                &ressources.metadata_path(&new_path),
+            ));
+        }
+
+        let _ = ressources
+            .backend
+            .rename(&backup_path, &meta_ressource.backup_path())
+            .await;
+
+        let new_metadata_path = ressources.metadata_path(&new_path);
+        let mut files = vec![new_metadata_path.as_path()];
+        if let Some(parent) = new_metadata_path.parent() {
+            files.push(parent);
+        }
+        ressources.sync(&new_path, &files).await?;
+        ressources
+            .journal(JournalOperation::Move {
+                from: path,
+                to: new_path.clone(),
+            })
+            .await?;
+
+        Ok(new_path)
+    }
+
+    pub async fn read_range<B: Backend>(
+        ressources: &Ressources<B>,
+        path: RessourcePath,
+        start: u64,
+        len: u64,
+    ) -> RessourceResult<Vec<u8>> {
+        let meta = MetaRessource::<T>::load(ressources, path.clone()).await?;
+        let data_path = meta.find_data_path(ressources).await;
+        let read_error = |e| RessourceError::read_data_io(e, &path, &data_path);
+
+        #[cfg(feature = "compression")]
+        if meta.is_compressed_fallback(&data_path) {
+            let compressed = ressources
+                .backend
+                .read(&data_path)
+                .await
+                .map_err(read_error)?;
+            let data = crate::compressed::gunzip(&compressed).map_err(read_error)?;
+            return crate::backend::BufferBackend::with_data(data)
+                .read_range(&data_path, start, len)
+                .await
+                .map_err(read_error);
+        }
+
+        ressources
+            .backend
+            .read_range(&data_path, start, len)
+            .await
+            .map_err(read_error)
+    }
+
+    pub async fn etag<B: Backend>(
+        ressources: &Ressources<B>,
+        path: RessourcePath,
+    ) -> RessourceResult<String> {
+        let meta = MetaRessource::<T>::load(ressources, path.clone()).await?;
+        let size = if meta.metadata.type_id == FolderRessource::id() {
+            0
+        } else {
+            let data_path = meta.find_data_path(ressources).await;
+            ressources
+                .backend
+                .len(&data_path)
+                .await
+                .map_err(|e| RessourceError::read_data_io(e, &path, &data_path))?
+        };
+
+        let modified = meta.metadata.last_modified();
+        Ok(format!(
+            "{:x}.{:x}-{size:x}",
+            modified.timestamp(),
+            modified.timestamp_subsec_nanos()
+        ))
+    }
 }
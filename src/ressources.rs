@@ -0,0 +1,836 @@
+use crate::{
+    alias_ressource::AliasRessource,
+    backend::{Backend, FsBackend, LimitedBackend, RetryBackend, RetryPolicy},
+    error::{RessourceError, RessourceResult, WriteDataError},
+    folder_ressource::FolderRessource,
+    index::IndexCache,
+    journal::JournalOperation,
+    meta::{MetaRessource, RessourceMetadata},
+    path::{NamingScheme, RessourceId, RessourcePath},
+    ressource::Ressource,
+    traits::{
+        AppendableRessource, PreviewableRessource, ReadableRessource, RessourceType,
+        WritableRessource,
+    },
+};
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt, future, lock::Mutex, stream};
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use uuid::Uuid;
+
+pub const MAX_ALIAS_DEPTH: usize = 32;
+
+#[derive(Debug, Clone)]
+pub struct Ressources<B: Backend = FsBackend> {
+    pub root: PathBuf,
+    pub backend: B,
+    pub durable: bool,
+    pub journal: Option<PathBuf>,
+    pub max_data_bytes: Option<u64>,
+    pub naming: NamingScheme,
+    pub lenient_filenames: bool,
+    /// Keep exactly one `.bak` copy of the previous data on `Ressource::update`, replacing any older backup.
+    pub backups: bool,
+    pub retry: Option<RetryPolicy>,
+    pub read_only: bool,
+    pub pretty_metadata: bool,
+    pub allowed_types: Option<HashSet<String>>,
+    pub(crate) index_lock: Arc<Mutex<()>>,
+}
+
+impl Ressources<FsBackend> {
+    pub fn new(root: PathBuf) -> Self {
+        Ressources {
+            root,
+            backend: FsBackend,
+            durable: false,
+            journal: None,
+            max_data_bytes: None,
+            naming: NamingScheme::default(),
+            lenient_filenames: false,
+            backups: false,
+            retry: None,
+            read_only: false,
+            pretty_metadata: false,
+            allowed_types: None,
+            index_lock: Arc::default(),
+        }
+    }
+
+    pub fn open_read_only(root: PathBuf) -> Self {
+        Ressources {
+            read_only: true,
+            ..Self::new(root)
+        }
+    }
+
+    pub async fn init(root: PathBuf) -> RessourceResult<Self> {
+        Self::init_with_backend(root, FsBackend).await
+    }
+}
+
+impl<B: Backend> Ressources<B> {
+    pub fn with_backend(root: PathBuf, backend: B) -> Self {
+        Ressources {
+            root,
+            backend,
+            durable: false,
+            journal: None,
+            max_data_bytes: None,
+            naming: NamingScheme::default(),
+            lenient_filenames: false,
+            backups: false,
+            retry: None,
+            read_only: false,
+            pretty_metadata: false,
+            allowed_types: None,
+            index_lock: Arc::default(),
+        }
+    }
+
+    pub async fn init_with_backend(root: PathBuf, backend: B) -> RessourceResult<Self> {
+        let ressources = Self::with_backend(root, backend);
+        let root_path = ressources.path(Vec::new());
+
+        if ressources.is_empty_dir(&root_path).await? {
+            ressources.init_root_folder().await?;
+            return Ok(ressources);
+        }
+
+        FolderRessource::new()
+            .write(&ressources.backend, &ressources.root)
+            .await
+            .map_err(|e| WriteDataError::new(FolderRessource::id(), &root_path, e))?;
+
+        ressources.init_root_folder().await?;
+        Ok(ressources)
+    }
+
+    async fn is_empty_dir(&self, root_path: &RessourcePath) -> RessourceResult<bool> {
+        let read_error = |e| RessourceError::read_data_io(e, root_path, &self.root);
+
+        if !self.backend.is_dir(&self.root).await.map_err(read_error)? {
+            return Ok(false);
+        }
+
+        let mut entries = self
+            .backend
+            .read_dir(&self.root)
+            .await
+            .map_err(read_error)?;
+        Ok(entries.next().await.is_none())
+    }
+
+    async fn init_root_folder(&self) -> RessourceResult<()> {
+        MetaRessource::<FolderRessource>::root(self.root.clone())
+            .write(self)
+            .await
+    }
+
+    pub(crate) fn check_writable(&self, path: &RessourcePath) -> RessourceResult<()> {
+        if self.read_only {
+            return Err(RessourceError::ReadOnly {
+                ressource_path: path.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn check_type(&self, path: &RessourcePath, type_id: &str) -> RessourceResult<()> {
+        match &self.allowed_types {
+            Some(allowed) if !allowed.contains(type_id) => Err(RessourceError::DisallowedType {
+                ressource_path: path.clone(),
+                ressource_type: type_id.to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_id(&self, path: &RessourcePath) -> RessourceResult<()> {
+        let Some(id) = path.path.last() else {
+            return Ok(());
+        };
+
+        match NamingScheme::reserved_suffix(id) {
+            Some(suffix) => Err(RessourceError::ReservedIdSuffix {
+                id: id.clone(),
+                ressource_path: path.clone(),
+                suffix,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) async fn replace_data<E>(
+        &self,
+        path: &RessourcePath,
+        data_path: &Path,
+        write: impl AsyncFnOnce(&LimitedBackend<'_, RetryBackend<'_, B>>, &Path) -> Result<(), E>,
+        error: impl FnOnce(E) -> RessourceError,
+    ) -> RessourceResult<()> {
+        let temp_path = data_path.with_added_extension("tmp");
+        let backend = RetryBackend::new(&self.backend, self.retry);
+        let limit = self.max_data_bytes.unwrap_or(u64::MAX);
+        let limited = LimitedBackend::new(&backend, limit);
+
+        if let Err(e) = write(&limited, &temp_path).await {
+            let _ = self.backend.remove_file(&temp_path).await;
+            if limited.exceeded() {
+                return Err(RessourceError::data_too_large(path, limit));
+            }
+            return Err(error(e));
+        }
+
+        if let Err(e) = backend.rename(&temp_path, data_path).await {
+            let _ = self.backend.remove_file(&temp_path).await;
+            return Err(RessourceError::rename_io(e, path, &temp_path, data_path));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) async fn sync(&self, path: &RessourcePath, files: &[&Path]) -> RessourceResult<()> {
+        if !self.durable {
+            return Ok(());
+        }
+
+        for file in files {
+            self.backend
+                .sync(file)
+                .await
+                .map_err(|e| RessourceError::sync(e, path, file))?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn exists(&self, path: &RessourcePath) -> RessourceResult<bool> {
+        self.metadata_file_exists(path)
+            .await
+            .map_err(|e| RessourceError::exists_io(e, path))
+    }
+
+    pub async fn read_metadata_bytes(&self, path: &RessourcePath) -> RessourceResult<Vec<u8>> {
+        self.read_metadata_file(path)
+            .await
+            .map_err(|e| RessourceError::metadata_io(e, path))
+    }
+
+    pub(crate) async fn check_parent(&self, path: &RessourcePath) -> RessourceResult<()> {
+        let mut parent_ressource = path.clone();
+        parent_ressource
+            .up()
+            .ok_or_else(|| RessourceError::ressource_at_root(path))?;
+
+        if parent_ressource.path.is_empty() {
+            let root = parent_ressource.resolve();
+            let is_dir = self
+                .backend
+                .is_dir(&root)
+                .await
+                .map_err(|e| RessourceError::root_io(e, path, &root))?;
+
+            if !is_dir {
+                return Err(RessourceError::root_not_a_directory(path, &root));
+            }
+        } else {
+            match Ressource::<FolderRessource>::load(self, parent_ressource.clone()).await {
+                Ok(_) => {}
+                Err(RessourceError::TypeMismatch { ressource_type, .. }) => {
+                    return Err(RessourceError::parent_not_a_folder(
+                        path,
+                        parent_ressource,
+                        ressource_type,
+                    ));
+                }
+                Err(e) => {
+                    if let Some(ancestor) = self.first_missing_ancestor(&parent_ressource).await? {
+                        return Err(RessourceError::missing_ancestor(path, ancestor));
+                    }
+
+                    return Err(RessourceError::parent_ressource(path, e));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn first_missing_ancestor(
+        &self,
+        path: &RessourcePath,
+    ) -> RessourceResult<Option<RessourcePath>> {
+        let mut ancestor = path.clone();
+        ancestor.path.clear();
+        for id in path.components_iter() {
+            ancestor.push(id.clone());
+            if !self.exists(&ancestor).await? {
+                return Ok(Some(ancestor));
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub async fn create_unique<T: WritableRessource>(
+        &self,
+        parent: &RessourcePath,
+        base_id: &str,
+        data: T,
+    ) -> RessourceResult<RessourcePath> {
+        let mut path = parent.with_child(base_id);
+        let mut suffix = 0;
+        loop {
+            match Ressource::create(self, path.clone(), &data).await {
+                Err(RessourceError::RessourceExists { .. }) => {
+                    suffix += 1;
+                    path = parent.with_child(format!("{base_id}-{suffix}"));
+                }
+                result => return result.map(|_| path),
+            }
+        }
+    }
+
+    pub fn new_id() -> RessourceId {
+        Uuid::new_v4().to_string()
+    }
+
+    pub async fn create_auto<T: WritableRessource>(
+        &self,
+        parent: &RessourcePath,
+        data: T,
+    ) -> RessourceResult<RessourcePath> {
+        let path = parent.with_child(Self::new_id());
+        Ressource::new(self, path.clone(), data).await?;
+        Ok(path)
+    }
+
+    pub async fn resolve_alias(&self, path: RessourcePath) -> RessourceResult<RessourcePath> {
+        let mut current = path.clone();
+        for _ in 0..=MAX_ALIAS_DEPTH {
+            let metadata = RessourceMetadata::load(self, &current).await?;
+            if metadata.type_id != AliasRessource::id() {
+                return Ok(current);
+            }
+
+            current = Ressource::<AliasRessource>::load(self, current)
+                .await?
+                .data
+                .target
+                .with_root(self.root.clone());
+        }
+
+        Err(RessourceError::AliasDepthExceeded {
+            ressource_path: path,
+            depth: MAX_ALIAS_DEPTH,
+        })
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            err,
+            fields(path = %path.resolve().display())
+        )
+    )]
+    pub async fn delete(&self, path: RessourcePath) -> RessourceResult<()> {
+        if path.path.is_empty() {
+            return Err(RessourceError::ressource_at_root(&path));
+        }
+
+        self.check_writable(&path)?;
+        let metadata = RessourceMetadata::load(self, &path).await?;
+        self.remove_tree(&path, metadata).await?;
+        self.finish_delete(path).await
+    }
+
+    pub(crate) async fn remove_tree(
+        &self,
+        path: &RessourcePath,
+        metadata: RessourceMetadata,
+    ) -> RessourceResult<()> {
+        let mut entries = if metadata.type_id == FolderRessource::id() {
+            self.walk(path.clone()).await?
+        } else {
+            Vec::new()
+        };
+        entries.reverse();
+        entries.push((path.clone(), metadata));
+
+        for (entry, metadata) in &entries {
+            self.delete_entry(entry, metadata).await?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            err,
+            fields(path = %path.resolve().display())
+        )
+    )]
+    pub async fn delete_empty(&self, path: RessourcePath) -> RessourceResult<()> {
+        if path.path.is_empty() {
+            return Err(RessourceError::ressource_at_root(&path));
+        }
+
+        self.check_writable(&path)?;
+        let metadata = RessourceMetadata::load(self, &path).await?;
+        if metadata.type_id == FolderRessource::id() {
+            let folder = Ressource::<FolderRessource>::load(self, path.clone()).await?;
+            if !folder.data.ressources.is_empty() {
+                return Err(RessourceError::FolderNotEmpty {
+                    ressource_path: path,
+                    children: folder.data.ressources.len(),
+                });
+            }
+        }
+
+        self.delete_entry(&path, &metadata).await?;
+        self.finish_delete(path).await
+    }
+
+    async fn finish_delete(&self, path: RessourcePath) -> RessourceResult<()> {
+        if let Some(parent) = self.metadata_path(&path).parent() {
+            self.sync(&path, &[parent]).await?;
+        }
+        self.journal(JournalOperation::Delete { path }).await
+    }
+
+    pub async fn delete_many(
+        &self,
+        paths: Vec<RessourcePath>,
+    ) -> Vec<(RessourcePath, RessourceResult<()>)> {
+        let mut results = Vec::with_capacity(paths.len());
+        for path in paths {
+            let result = self.delete(path.clone()).await;
+            results.push((path, result));
+        }
+        results
+    }
+
+    async fn delete_entry(
+        &self,
+        path: &RessourcePath,
+        metadata: &RessourceMetadata,
+    ) -> RessourceResult<()> {
+        let data_path = metadata.data_path(path);
+        let removed = if metadata.type_id == FolderRessource::id() {
+            self.backend.remove_dir(&data_path).await
+        } else {
+            self.backend.remove_file(&data_path).await
+        };
+
+        match removed {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                return Err(RessourceError::delete_io(e, path, &data_path));
+            }
+            _ => {}
+        }
+
+        let backup_path = metadata.backup_path(path);
+        match self.backend.remove_file(&backup_path).await {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                return Err(RessourceError::delete_io(e, path, &backup_path));
+            }
+            _ => {}
+        }
+
+        self.remove_metadata_file(path)
+            .await
+            .map_err(|e| RessourceError::delete_io(e, path, &self.metadata_path(path)))
+    }
+
+    pub async fn dependents(&self, path: &RessourcePath) -> RessourceResult<Vec<RessourcePath>> {
+        let mut dependents = Vec::new();
+        for (entry, metadata) in self.walk(self.path(Vec::new())).await? {
+            if entry.path.starts_with(&path.path) {
+                continue;
+            }
+
+            let mut references = metadata.references();
+            if metadata.type_id == AliasRessource::id() {
+                let alias = Ressource::<AliasRessource>::load(self, entry.clone()).await?;
+                references.push(alias.data.target.path);
+            }
+
+            if references
+                .iter()
+                .any(|reference| reference.starts_with(&path.path))
+            {
+                dependents.push(entry);
+            }
+        }
+
+        Ok(dependents)
+    }
+
+    pub async fn delete_checked(&self, path: RessourcePath) -> RessourceResult<()> {
+        let dependents = self.dependents(&path).await?;
+        if !dependents.is_empty() {
+            return Err(RessourceError::HasDependents {
+                ressource_path: path,
+                dependents,
+            });
+        }
+
+        self.delete(path).await
+    }
+
+    pub fn path(&self, path: Vec<RessourceId>) -> RessourcePath {
+        RessourcePath::from_vec(self.root.clone(), path)
+    }
+
+    pub async fn resolve_str(&self, path: &str) -> RessourceResult<RessourcePath> {
+        let ressource_path = self.path(
+            path.split('/')
+                .filter(|id| !id.is_empty())
+                .map(str::to_string)
+                .collect(),
+        );
+
+        if let Some(id) = ressource_path
+            .components_iter()
+            .find(|id| !RessourcePath::is_valid_id(id))
+        {
+            return Err(RessourceError::invalid_ressource_id(id, &ressource_path));
+        }
+
+        let mut component = self.path(Vec::new());
+        for id in ressource_path
+            .path
+            .iter()
+            .take(ressource_path.path.len().saturating_sub(1))
+        {
+            component.push(id.clone());
+            let metadata = RessourceMetadata::load(self, &component).await?;
+            if metadata.type_id != FolderRessource::id() {
+                return Err(RessourceError::path_component_not_a_folder(
+                    &ressource_path,
+                    component,
+                    metadata.type_id,
+                ));
+            }
+        }
+
+        Ok(ressource_path)
+    }
+
+    pub async fn root_folder(&self) -> RessourceResult<Ressource<FolderRessource>> {
+        Ressource::load(self, self.path(Vec::new())).await
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            err,
+            fields(path = %path.resolve().display())
+        )
+    )]
+    pub async fn walk(
+        &self,
+        path: RessourcePath,
+    ) -> RessourceResult<Vec<(RessourcePath, RessourceMetadata)>> {
+        let mut folders = vec![path];
+        let mut entries = Vec::new();
+        let cache = IndexCache::default();
+
+        while let Some(folder) = folders.pop() {
+            let folder_ressource = Ressource::<FolderRessource>::load(self, folder.clone()).await?;
+            for id in folder_ressource.data.ressources {
+                let child = folder.with_child(id);
+                let metadata = RessourceMetadata::load_cached(self, &child, &cache).await?;
+                if metadata.type_id == FolderRessource::id() {
+                    folders.push(child.clone());
+                }
+                entries.push((child, metadata));
+            }
+        }
+
+        Ok(entries)
+    }
+
+    pub async fn latest_modified(
+        &self,
+        path: RessourcePath,
+    ) -> RessourceResult<Option<DateTime<Utc>>> {
+        Ok(self
+            .walk(path)
+            .await?
+            .iter()
+            .map(|(_, metadata)| metadata.last_modified())
+            .max())
+    }
+
+    pub async fn changed_since(
+        &self,
+        path: RessourcePath,
+        since: DateTime<Utc>,
+    ) -> RessourceResult<Vec<RessourcePath>> {
+        Ok(self
+            .walk(path)
+            .await?
+            .into_iter()
+            .filter(|(_, metadata)| metadata.last_modified() > since)
+            .map(|(path, _)| path)
+            .collect())
+    }
+
+    pub fn all_of_type<T: ReadableRessource>(
+        &self,
+        path: RessourcePath,
+    ) -> impl Stream<Item = RessourceResult<Ressource<T>>> + '_ {
+        stream::once(self.walk(path))
+            .map(move |entries| match entries {
+                Ok(entries) => stream::iter(entries)
+                    .filter(|(_, metadata)| future::ready(metadata.type_id == T::id()))
+                    .then(move |(path, _)| Ressource::<T>::load(self, path))
+                    .left_stream(),
+                Err(e) => stream::once(async { Err(e) }).right_stream(),
+            })
+            .flatten()
+    }
+
+    pub async fn preview<T: ReadableRessource + PreviewableRessource>(
+        &self,
+        path: RessourcePath,
+    ) -> RessourceResult<Vec<u8>> {
+        let ressource = Ressource::<T>::load(self, path).await?;
+        ressource
+            .data
+            .preview()
+            .await
+            .map_err(|e| RessourceError::Preview {
+                ressource_type: T::id(),
+                path: ressource.meta.path.resolve(),
+                ressource_path: ressource.meta.path.clone(),
+                error: Box::new(e),
+            })
+    }
+
+    pub async fn map_in_place<T: ReadableRessource + WritableRessource>(
+        &self,
+        path: RessourcePath,
+        f: impl Fn(T) -> T,
+    ) -> RessourceResult<Vec<RessourceError>> {
+        let mut errors = Vec::new();
+        for (entry, metadata) in self.walk(path).await? {
+            if metadata.type_id != T::id() {
+                continue;
+            }
+
+            let mapped = match Ressource::<T>::load(self, entry.clone()).await {
+                Ok(ressource) => Ressource::update(self, entry, f(ressource.data))
+                    .await
+                    .map(|_| ()),
+                Err(e) => Err(e),
+            };
+
+            if let Err(e) = mapped {
+                errors.push(e);
+            }
+        }
+
+        Ok(errors)
+    }
+
+    pub async fn load_metadata_many(
+        &self,
+        paths: Vec<RessourcePath>,
+        concurrency: usize,
+    ) -> Vec<RessourceResult<RessourceMetadata>> {
+        let cache = IndexCache::default();
+        stream::iter(paths)
+            .map(|path| {
+                let cache = &cache;
+                async move { RessourceMetadata::load_cached(self, &path, cache).await }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            err,
+            fields(ressource_type = T::id(), path = %path.resolve().display())
+        )
+    )]
+    pub async fn append<T: AppendableRessource>(
+        &self,
+        path: RessourcePath,
+        data: &[u8],
+    ) -> RessourceResult<()> {
+        self.check_writable(&path)?;
+        let mut meta_ressource = MetaRessource::<T>::load(self, path.clone()).await?;
+        let data_path = meta_ressource.find_data_path(self).await;
+        if meta_ressource.is_compressed_fallback(&data_path) {
+            return Err(RessourceError::AppendToCompressed {
+                ressource_path: path,
+                path: data_path,
+            });
+        }
+
+        let read_error = |e| RessourceError::read_data_io(e, &path, &data_path);
+
+        if let Some(limit) = self.max_data_bytes {
+            let size = self.backend.len(&data_path).await.map_err(read_error)?;
+            if size.saturating_add(data.len() as u64) > limit {
+                return Err(RessourceError::data_too_large(&path, limit));
+            }
+        }
+
+        if self
+            .backend
+            .link_count(&data_path)
+            .await
+            .map_err(read_error)?
+            > 1
+        {
+            let existing = self.backend.read(&data_path).await.map_err(read_error)?;
+            self.replace_data(
+                &path,
+                &data_path,
+                async |backend, temp_path| backend.write(temp_path, &existing).await,
+                |e| RessourceError::write_data_io(e, &path, &data_path),
+            )
+            .await?;
+        }
+
+        T::append(
+            &RetryBackend::new(&self.backend, self.retry),
+            &data_path,
+            data,
+        )
+        .await
+        .map_err(|e| WriteDataError::new(T::id(), &path, e))?;
+
+        self.sync(&path, &[data_path.as_path()]).await?;
+        meta_ressource.touch(self).await
+    }
+
+    pub async fn update_metadata_many(
+        &self,
+        paths: Vec<RessourcePath>,
+        update: impl Fn(&mut RessourceMetadata),
+    ) -> Vec<RessourceResult<RessourceMetadata>> {
+        let mut results = Vec::with_capacity(paths.len());
+        for path in paths {
+            results.push(self.update_metadata(&path, &update).await);
+        }
+        results
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &RessourcePath,
+        update: impl Fn(&mut RessourceMetadata),
+    ) -> RessourceResult<RessourceMetadata> {
+        self.check_writable(path)?;
+        let mut metadata = RessourceMetadata::load(self, path).await?;
+        update(&mut metadata);
+
+        let bytes = self
+            .metadata_json(&metadata)
+            .map_err(|e| RessourceError::metadata_serialize(e, path))?;
+        self.write_metadata_file(path, &bytes)
+            .await
+            .map_err(|e| RessourceError::write_metadata_io(e, path))?;
+        self.sync(path, &[self.metadata_path(path).as_path()])
+            .await?;
+        self.journal(JournalOperation::Update { path: path.clone() })
+            .await?;
+
+        Ok(metadata)
+    }
+
+    pub async fn exists_many(
+        &self,
+        paths: Vec<RessourcePath>,
+        concurrency: usize,
+    ) -> Vec<RessourceResult<bool>> {
+        stream::iter(paths)
+            .map(|path| async move { self.exists(&path).await })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    pub async fn type_histogram(
+        &self,
+        path: RessourcePath,
+    ) -> RessourceResult<HashMap<String, usize>> {
+        let mut histogram = HashMap::new();
+        for (_, metadata) in self.walk(path).await? {
+            *histogram.entry(metadata.type_id).or_insert(0) += 1;
+        }
+        Ok(histogram)
+    }
+
+    pub async fn retype(
+        &self,
+        path: RessourcePath,
+        old_id: &str,
+        new_id: &str,
+        dry_run: bool,
+    ) -> RessourceResult<Vec<RessourcePath>> {
+        if !dry_run {
+            self.check_writable(&path)?;
+            self.check_type(&path, new_id)?;
+        }
+
+        let mut retyped = Vec::new();
+        for (entry, mut metadata) in self.walk(path).await? {
+            if metadata.type_id != old_id {
+                continue;
+            }
+
+            if !dry_run {
+                metadata.type_id = new_id.to_string();
+                let metadata = self
+                    .metadata_json(&metadata)
+                    .map_err(|e| RessourceError::metadata_serialize(e, &entry))?;
+                self.write_metadata_file(&entry, &metadata)
+                    .await
+                    .map_err(|e| RessourceError::write_metadata_io(e, &entry))?;
+                self.sync(&entry, &[self.metadata_path(&entry).as_path()])
+                    .await?;
+                self.journal(JournalOperation::Update {
+                    path: entry.clone(),
+                })
+                .await?;
+            }
+
+            retyped.push(entry);
+        }
+        Ok(retyped)
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl Ressources<FsBackend> {
+    pub async fn temp() -> (Self, tempfile::TempDir) {
+        let dir = tempfile::TempDir::new().expect("Unable to create temporary vault directory");
+        let ressources = Ressources::init(dir.path().join("vault"))
+            .await
+            .expect("Unable to initialize temporary vault");
+        (ressources, dir)
+    }
+}
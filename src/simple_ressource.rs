@@ -0,0 +1,82 @@
+use crate::{
+    backend::Backend,
+    traits::{AppendableRessource, ReadableRessource, RessourceType, WritableRessource},
+};
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SimpleRessourceError {
+    #[error("SimpleRessource: IO Error reading data at {path}. Error: {error}")]
+    Reading {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+
+    #[error("SimpleRessource: IO Error writing data at {path}. Error: {error}")]
+    Writing {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+pub trait SimpleRessource: RessourceType + Send + Sync + Sized {
+    fn data_extension() -> &'static str;
+    fn from_bytes(data: Vec<u8>) -> Self;
+    fn to_bytes(&self) -> Cow<'_, [u8]>;
+}
+
+impl<T: SimpleRessource> ReadableRessource for T {
+    type Error = SimpleRessourceError;
+    async fn read<B: Backend>(backend: &B, path: &Path) -> Result<Self, SimpleRessourceError> {
+        backend
+            .read(path)
+            .await
+            .map(T::from_bytes)
+            .map_err(|e| SimpleRessourceError::Reading {
+                path: path.to_path_buf(),
+                error: e,
+            })
+    }
+}
+
+impl<T: SimpleRessource> WritableRessource for T {
+    type Error = SimpleRessourceError;
+    async fn write<B: Backend>(
+        &self,
+        backend: &B,
+        path: &Path,
+    ) -> Result<(), SimpleRessourceError> {
+        backend
+            .write(path, &self.to_bytes())
+            .await
+            .map_err(|e| SimpleRessourceError::Writing {
+                path: path.to_path_buf(),
+                error: e,
+            })
+    }
+
+    fn data_extension() -> &'static str {
+        <T as SimpleRessource>::data_extension()
+    }
+}
+
+impl<T: SimpleRessource> AppendableRessource for T {
+    type Error = SimpleRessourceError;
+    async fn append<B: Backend>(
+        backend: &B,
+        path: &Path,
+        data: &[u8],
+    ) -> Result<(), SimpleRessourceError> {
+        backend
+            .append(path, data)
+            .await
+            .map_err(|e| SimpleRessourceError::Writing {
+                path: path.to_path_buf(),
+                error: e,
+            })
+    }
+}
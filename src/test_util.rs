@@ -0,0 +1,23 @@
+use crate::{
+    backend::FsBackend,
+    traits::{ReadableRessource, WritableRessource},
+};
+use std::fmt::Debug;
+
+pub async fn test_roundtrip<T>(value: T)
+where
+    T: ReadableRessource + WritableRessource + PartialEq + Debug,
+{
+    let dir = tempfile::TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join(format!("value.{}", T::data_extension()));
+
+    value
+        .write(&FsBackend, &path)
+        .await
+        .expect("Unable to write value");
+    let read = T::read(&FsBackend, &path)
+        .await
+        .expect("Unable to read value back");
+
+    assert_eq!(value, read, "Value changed after a write/read roundtrip");
+}
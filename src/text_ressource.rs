@@ -0,0 +1,115 @@
+use crate::{
+    backend::Backend,
+    traits::{AppendableRessource, ReadableRessource, RessourceType, WritableRessource},
+};
+use std::{
+    path::{Path, PathBuf},
+    string::FromUtf8Error,
+};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum TextRessourceError {
+    #[error("TextRessource: IO Error reading data at {path}. Error: {error}")]
+    Reading {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+
+    #[error("TextRessource: Data at {path} is not valid UTF-8 after byte {offset}. Error: {error}")]
+    InvalidUtf8 {
+        path: PathBuf,
+        offset: usize,
+        error: FromUtf8Error,
+    },
+
+    #[error("TextRessource: IO Error writing data at {path}. Error: {error}")]
+    Writing {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextRessource {
+    pub text: String,
+}
+
+impl TextRessource {
+    pub fn new(text: String) -> Self {
+        TextRessource { text }
+    }
+}
+
+impl RessourceType for TextRessource {
+    fn id() -> &'static str {
+        "core/text"
+    }
+
+    fn display_name() -> &'static str {
+        "Text"
+    }
+}
+
+impl ReadableRessource for TextRessource {
+    type Error = TextRessourceError;
+    async fn read<B: Backend>(backend: &B, path: &Path) -> Result<Self, TextRessourceError> {
+        let data = backend
+            .read(path)
+            .await
+            .map_err(|e| TextRessourceError::Reading {
+                path: path.to_path_buf(),
+                error: e,
+            })?;
+
+        String::from_utf8(data)
+            .map(Self::new)
+            .map_err(|e| TextRessourceError::InvalidUtf8 {
+                path: path.to_path_buf(),
+                offset: e.utf8_error().valid_up_to(),
+                error: e,
+            })
+    }
+}
+
+impl WritableRessource for TextRessource {
+    type Error = TextRessourceError;
+    async fn write<B: Backend>(&self, backend: &B, path: &Path) -> Result<(), TextRessourceError> {
+        backend
+            .write(path, self.text.as_bytes())
+            .await
+            .map_err(|e| TextRessourceError::Writing {
+                path: path.to_path_buf(),
+                error: e,
+            })
+    }
+
+    fn data_extension() -> &'static str {
+        "txt"
+    }
+
+    fn mime_type() -> Option<&'static str> {
+        Some("text/plain; charset=utf-8")
+    }
+
+    fn estimated_size(&self) -> Option<u64> {
+        Some(self.text.len() as u64)
+    }
+}
+
+impl AppendableRessource for TextRessource {
+    type Error = TextRessourceError;
+    async fn append<B: Backend>(
+        backend: &B,
+        path: &Path,
+        data: &[u8],
+    ) -> Result<(), TextRessourceError> {
+        backend
+            .append(path, data)
+            .await
+            .map_err(|e| TextRessourceError::Writing {
+                path: path.to_path_buf(),
+                error: e,
+            })
+    }
+}
@@ -1,7 +1,11 @@
+use crate::{backend::Backend, ressources::Ressources};
 use std::path::Path;
 
 pub trait RessourceType {
     fn id() -> &'static str;
+    fn display_name() -> &'static str {
+        Self::id()
+    }
 }
 
 pub trait ReadableRessource: RessourceType
@@ -9,9 +13,21 @@ where
     Self::Error: 'static,
 {
     type Error: std::error::Error;
-    fn read(path: &Path) -> impl Future<Output = Result<Self, Self::Error>> + Send
+    fn read<B: Backend>(
+        backend: &B,
+        path: &Path,
+    ) -> impl Future<Output = Result<Self, Self::Error>> + Send
     where
         Self: Sized;
+    fn read_in<B: Backend>(
+        ressources: &Ressources<B>,
+        path: &Path,
+    ) -> impl Future<Output = Result<Self, Self::Error>> + Send
+    where
+        Self: Sized,
+    {
+        Self::read(&ressources.backend, path)
+    }
 }
 
 pub trait WritableRessource: RessourceType
@@ -20,5 +36,41 @@ where
 {
     type Error: std::error::Error;
     fn data_extension() -> &'static str;
-    fn write(&self, path: &Path) -> impl Future<Output = Result<(), Self::Error>> + Send;
+    fn extra_metadata() -> serde_json::Map<String, serde_json::Value> {
+        serde_json::Map::new()
+    }
+    fn mime_type() -> Option<&'static str> {
+        None
+    }
+    fn mime(&self) -> Option<&str> {
+        Self::mime_type()
+    }
+    fn estimated_size(&self) -> Option<u64> {
+        None
+    }
+    fn write<B: Backend>(
+        &self,
+        backend: &B,
+        path: &Path,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+pub trait AppendableRessource: RessourceType
+where
+    Self::Error: 'static,
+{
+    type Error: std::error::Error;
+    fn append<B: Backend>(
+        backend: &B,
+        path: &Path,
+        data: &[u8],
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+pub trait PreviewableRessource: RessourceType
+where
+    Self::Error: 'static,
+{
+    type Error: std::error::Error;
+    fn preview(&self) -> impl Future<Output = Result<Vec<u8>, Self::Error>> + Send;
 }
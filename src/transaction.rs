@@ -0,0 +1,153 @@
+use crate::{
+    backend::Backend, error::RessourceResult, meta::RessourceMetadata, path::RessourcePath,
+    ressource::Ressource, ressources::Ressources, traits::WritableRessource,
+};
+use std::sync::{Mutex, MutexGuard};
+
+#[derive(Debug)]
+pub struct Transaction<'a, B: Backend> {
+    ressources: &'a Ressources<B>,
+    created: Mutex<Vec<RessourcePath>>,
+    deleted: Mutex<Vec<RessourcePath>>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl<'a, B: Backend> Transaction<'a, B> {
+    fn new(ressources: &'a Ressources<B>) -> Self {
+        Transaction {
+            ressources,
+            created: Mutex::new(Vec::new()),
+            deleted: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn ressources(&self) -> &'a Ressources<B> {
+        self.ressources
+    }
+
+    pub async fn create<T: WritableRessource>(
+        &self,
+        path: RessourcePath,
+        data: T,
+    ) -> RessourceResult<Ressource<T>> {
+        let ressource = Ressource::new(self.ressources, path.clone(), data).await?;
+        lock(&self.created).push(path);
+        Ok(ressource)
+    }
+
+    pub async fn delete(&self, path: RessourcePath) -> RessourceResult<()> {
+        RessourceMetadata::load(self.ressources, &path).await?;
+        lock(&self.deleted).push(path);
+        Ok(())
+    }
+
+    async fn commit(&self) -> RessourceResult<()> {
+        let deleted = std::mem::take(&mut *lock(&self.deleted));
+        for path in deleted {
+            self.ressources.delete(path).await?;
+        }
+        Ok(())
+    }
+
+    async fn rollback(&self) {
+        let created = std::mem::take(&mut *lock(&self.created));
+        for path in created.into_iter().rev() {
+            let _ = self.ressources.delete(path).await;
+        }
+    }
+}
+
+impl<B: Backend> Ressources<B> {
+    pub async fn transaction<R>(
+        &self,
+        f: impl AsyncFnOnce(&Transaction<'_, B>) -> RessourceResult<R>,
+    ) -> RessourceResult<R> {
+        let transaction = Transaction::new(self);
+        let result = match f(&transaction).await {
+            Ok(value) => transaction.commit().await.map(|_| value),
+            Err(e) => Err(e),
+        };
+
+        if result.is_err() {
+            transaction.rollback().await;
+        }
+        result
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod tests {
+    use crate::{
+        error::RessourceError, folder_ressource::FolderRessource, memory_backend::MemoryBackend,
+        ressource::Ressource, ressources::Ressources, text_ressource::TextRessource,
+    };
+    use futures::executor::block_on;
+
+    #[test]
+    fn failed_transaction_rolls_back_creates_and_keeps_deletes() {
+        block_on(async {
+            let ressources = Ressources::init_with_backend("/vault".into(), MemoryBackend::new())
+                .await
+                .unwrap();
+            let kept = ressources.path(vec!["kept".into()]);
+            Ressource::new(&ressources, kept.clone(), TextRessource::new("kept".into()))
+                .await
+                .unwrap();
+
+            let folder = ressources.path(vec!["f".into()]);
+            let child = folder.with_child("a");
+            let result = ressources
+                .transaction(async |transaction| {
+                    transaction
+                        .create(folder.clone(), FolderRessource::new())
+                        .await?;
+                    transaction
+                        .create(child.clone(), TextRessource::new("a".into()))
+                        .await?;
+                    transaction.delete(kept.clone()).await?;
+                    transaction
+                        .create(child.clone(), TextRessource::new("again".into()))
+                        .await
+                })
+                .await;
+
+            assert!(matches!(
+                result,
+                Err(RessourceError::RessourceExists { .. })
+            ));
+            assert!(!ressources.exists(&child).await.unwrap());
+            assert!(!ressources.exists(&folder).await.unwrap());
+            assert!(ressources.exists(&kept).await.unwrap());
+        });
+    }
+
+    #[test]
+    fn successful_transaction_applies_deletes() {
+        block_on(async {
+            let ressources = Ressources::init_with_backend("/vault".into(), MemoryBackend::new())
+                .await
+                .unwrap();
+            let old = ressources.path(vec!["old".into()]);
+            let new = ressources.path(vec!["new".into()]);
+            Ressource::new(&ressources, old.clone(), TextRessource::new("old".into()))
+                .await
+                .unwrap();
+
+            ressources
+                .transaction(async |transaction| {
+                    transaction
+                        .create(new.clone(), TextRessource::new("new".into()))
+                        .await?;
+                    transaction.delete(old.clone()).await
+                })
+                .await
+                .unwrap();
+
+            assert!(ressources.exists(&new).await.unwrap());
+            assert!(!ressources.exists(&old).await.unwrap());
+        });
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{
+    backend::Backend,
+    error::{RessourceError, RessourceResult},
+    folder_ressource::FolderRessource,
+    path::{NamingScheme, RessourcePath},
+    ressource::Ressource,
+    ressources::Ressources,
+    traits::RessourceType,
+};
+use futures::StreamExt;
+use std::{
+    collections::HashSet,
+    ffi::OsStr,
+    iter,
+    path::{Path, PathBuf},
+};
+
+fn is_backup_name(name: &OsStr) -> bool {
+    name.to_str()
+        .and_then(|name| name.strip_suffix(".bak"))
+        .is_some_and(|data_name| {
+            data_name.ends_with(NamingScheme::DATA_SUFFIX)
+                || data_name.contains(&format!("{}.", NamingScheme::DATA_SUFFIX))
+        })
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct VacuumReport {
+    pub removed_folders: Vec<RessourcePath>,
+    pub removed_backups: Vec<PathBuf>,
+    pub compacted: Vec<RessourcePath>,
+    pub reclaimed_bytes: u64,
+}
+
+impl<B: Backend> Ressources<B> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            err,
+            fields(path = %path.resolve().display())
+        )
+    )]
+    pub async fn vacuum(
+        &self,
+        path: RessourcePath,
+        compact_metadata: bool,
+    ) -> RessourceResult<VacuumReport> {
+        self.check_writable(&path)?;
+
+        let entries = self.walk(path.clone()).await?;
+        let mut report = VacuumReport::default();
+
+        let live: HashSet<PathBuf> = entries
+            .iter()
+            .flat_map(|(entry, metadata)| [metadata.data_path(entry), metadata.backup_path(entry)])
+            .collect();
+        let mut folders: Vec<&RessourcePath> = entries
+            .iter()
+            .filter(|(_, metadata)| metadata.type_id == FolderRessource::id())
+            .map(|(entry, _)| entry)
+            .collect();
+
+        for folder in iter::once(&path).chain(folders.iter().copied()) {
+            let dir = self.naming.data_path(folder, "");
+            self.remove_orphaned_backups(folder, &dir, &live, &mut report)
+                .await?;
+        }
+
+        folders.sort_by_key(|folder| std::cmp::Reverse(folder.path.len()));
+        for folder in folders {
+            let children = Ressource::<FolderRessource>::load(self, folder.clone()).await?;
+            if !children.data.ressources.is_empty() {
+                continue;
+            }
+
+            let metadata_size = self.read_metadata_bytes(folder).await?.len() as u64;
+            self.delete_empty(folder.clone()).await?;
+            report.reclaimed_bytes += metadata_size;
+            report.removed_folders.push(folder.clone());
+        }
+
+        if compact_metadata {
+            for (entry, _) in &entries {
+                if report.removed_folders.contains(entry) {
+                    continue;
+                }
+
+                let bytes = self.read_metadata_bytes(entry).await?;
+                let value: serde_json::Value = serde_json::from_slice(&bytes)
+                    .map_err(|e| RessourceError::metadata_format(e, entry))?;
+                let compacted = serde_json::to_vec(&value)
+                    .map_err(|e| RessourceError::metadata_serialize(e, entry))?;
+                if compacted.len() >= bytes.len() {
+                    continue;
+                }
+
+                self.write_metadata_file(entry, &compacted)
+                    .await
+                    .map_err(|e| RessourceError::write_metadata_io(e, entry))?;
+                self.sync(entry, &[self.metadata_path(entry).as_path()])
+                    .await?;
+                report.reclaimed_bytes += (bytes.len() - compacted.len()) as u64;
+                report.compacted.push(entry.clone());
+            }
+        }
+
+        Ok(report)
+    }
+
+    async fn remove_orphaned_backups(
+        &self,
+        folder: &RessourcePath,
+        dir: &Path,
+        live: &HashSet<PathBuf>,
+        report: &mut VacuumReport,
+    ) -> RessourceResult<()> {
+        let list_error = |e| RessourceError::read_data_io(e, folder, dir);
+        let mut names = self.backend.read_dir(dir).await.map_err(list_error)?;
+
+        while let Some(name) = names.next().await {
+            let name = name.map_err(list_error)?;
+            let backup_path = dir.join(&name);
+            if !is_backup_name(&name) || live.contains(&backup_path) {
+                continue;
+            }
+
+            let delete_error = |e| RessourceError::delete_io(e, folder, &backup_path);
+            let size = self.backend.len(&backup_path).await.map_err(delete_error)?;
+            self.backend
+                .remove_file(&backup_path)
+                .await
+                .map_err(delete_error)?;
+
+            report.reclaimed_bytes += size;
+            report.removed_backups.push(backup_path);
+        }
+
+        Ok(())
+    }
+}
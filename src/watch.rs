@@ -0,0 +1,214 @@
+use crate::{
+    backend::FsBackend,
+    error::{RessourceError, RessourceResult},
+    path::{NamingScheme, RessourcePath},
+    ressources::Ressources,
+};
+use futures::{
+    Stream, StreamExt,
+    channel::mpsc::{UnboundedReceiver, unbounded},
+};
+use notify::{
+    Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher,
+    event::{ModifyKind, RenameMode},
+};
+use std::{
+    path::{Component, Path},
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tokio::time::{Sleep, sleep_until};
+
+pub const WATCH_DEBOUNCE: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeEvent {
+    Created(RessourcePath),
+    Modified(RessourcePath),
+    Removed(RessourcePath),
+}
+
+impl ChangeEvent {
+    pub fn path(&self) -> &RessourcePath {
+        match self {
+            ChangeEvent::Created(path)
+            | ChangeEvent::Modified(path)
+            | ChangeEvent::Removed(path) => path,
+        }
+    }
+
+    fn merge(self, next: ChangeEvent) -> ChangeEvent {
+        match (self, next) {
+            (ChangeEvent::Created(path), ChangeEvent::Modified(_)) => ChangeEvent::Created(path),
+            (ChangeEvent::Removed(_), ChangeEvent::Created(path)) => ChangeEvent::Modified(path),
+            (_, next) => next,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Watch {
+    _watcher: RecommendedWatcher,
+    receiver: UnboundedReceiver<(Instant, ChangeEvent)>,
+    pending: Vec<(Instant, ChangeEvent)>,
+    delay: Option<Pin<Box<Sleep>>>,
+    closed: bool,
+}
+
+impl Watch {
+    fn push(&mut self, at: Instant, event: ChangeEvent) {
+        let pending = self.pending.iter_mut().find(|(pending_at, pending)| {
+            pending.path() == event.path() && at.duration_since(*pending_at) < WATCH_DEBOUNCE
+        });
+
+        match pending {
+            Some((_, pending)) => *pending = pending.clone().merge(event),
+            None => self.pending.push((at, event)),
+        }
+    }
+}
+
+impl Stream for Watch {
+    type Item = ChangeEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<ChangeEvent>> {
+        while !self.closed {
+            match self.receiver.poll_next_unpin(cx) {
+                Poll::Ready(Some((at, event))) => self.push(at, event),
+                Poll::Ready(None) => self.closed = true,
+                Poll::Pending => break,
+            }
+        }
+
+        let Some(&(at, _)) = self.pending.first() else {
+            self.delay = None;
+            return if self.closed {
+                Poll::Ready(None)
+            } else {
+                Poll::Pending
+            };
+        };
+
+        let deadline = (at + WATCH_DEBOUNCE).into();
+        let delay = self
+            .delay
+            .get_or_insert_with(|| Box::pin(sleep_until(deadline)));
+        if delay.deadline() != deadline {
+            delay.as_mut().reset(deadline);
+        }
+        if delay.as_mut().poll(cx).is_pending() && !self.closed {
+            return Poll::Pending;
+        }
+
+        self.delay = None;
+        Poll::Ready(Some(self.pending.remove(0).1))
+    }
+}
+
+fn ressource_path(
+    base: &RessourcePath,
+    naming: NamingScheme,
+    path: &Path,
+) -> Option<RessourcePath> {
+    let mut components = path
+        .strip_prefix(&base.root)
+        .ok()?
+        .components()
+        .map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let name = components.pop()?;
+    let id = match naming.id_from_metadata_file_name(name) {
+        Some(id) => id,
+        None => naming
+            .id_from_folder_name(name)
+            .or_else(|| Some(name.rsplit_once(".data.")?.0))?,
+    };
+
+    let mut ids = components
+        .into_iter()
+        .map(|folder| naming.id_from_folder_name(folder).map(str::to_string))
+        .collect::<Option<Vec<_>>>()?;
+    ids.push(id.to_string());
+
+    (ids.starts_with(&base.path) && ids.len() > base.path.len())
+        .then(|| RessourcePath::from_vec(base.root.clone(), ids))
+}
+
+fn change_events(base: &RessourcePath, naming: NamingScheme, event: Event) -> Vec<ChangeEvent> {
+    let paths = event
+        .paths
+        .iter()
+        .map(|path| ressource_path(base, naming, path))
+        .collect::<Vec<_>>();
+
+    match (event.kind, paths.as_slice()) {
+        (EventKind::Modify(ModifyKind::Name(RenameMode::Both)), [from, to]) => from
+            .clone()
+            .map(ChangeEvent::Removed)
+            .into_iter()
+            .chain(to.clone().map(ChangeEvent::Created))
+            .collect(),
+        (EventKind::Modify(ModifyKind::Name(RenameMode::From)), _) | (EventKind::Remove(_), _) => {
+            paths
+                .into_iter()
+                .flatten()
+                .map(ChangeEvent::Removed)
+                .collect()
+        }
+        (EventKind::Modify(ModifyKind::Name(RenameMode::To)), _) | (EventKind::Create(_), _) => {
+            paths
+                .into_iter()
+                .flatten()
+                .map(ChangeEvent::Created)
+                .collect()
+        }
+        (EventKind::Modify(_), _) => paths
+            .into_iter()
+            .flatten()
+            .map(ChangeEvent::Modified)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+impl Ressources<FsBackend> {
+    pub fn watch(&self, path: RessourcePath) -> RessourceResult<Watch> {
+        let (sender, receiver) = unbounded();
+        let base = path.clone();
+        let naming = self.naming;
+        let watched = naming.data_path(&base, "");
+
+        let watch_error = |e| RessourceError::Watch {
+            error: Box::new(e),
+            ressource_path: path.clone(),
+            path: watched.clone(),
+        };
+
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            let Ok(event) = event else {
+                return;
+            };
+            let at = Instant::now();
+            for change in change_events(&base, naming, event) {
+                let _ = sender.unbounded_send((at, change));
+            }
+        })
+        .map_err(watch_error)?;
+        watcher
+            .watch(&watched, RecursiveMode::Recursive)
+            .map_err(watch_error)?;
+
+        Ok(Watch {
+            _watcher: watcher,
+            receiver,
+            pending: Vec::new(),
+            delay: None,
+            closed: false,
+        })
+    }
+}